@@ -1,5 +1,13 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Bound,
+    time::Duration,
+};
 
+use base64::prelude::*;
+use futures::{StreamExt, TryStreamExt};
+use opendal_util::Copier;
+use restate_sdk::errors::TerminalError;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use url::Url;
@@ -11,72 +19,2025 @@ mod sealed {
 
     pub trait Sealed {
         fn example() -> Self;
+        fn example_destination() -> Self;
     }
 
     impl Sealed for Url {
         fn example() -> Self {
             Url::parse("https://example.com/path/to/file.pdf").unwrap()
         }
+        fn example_destination() -> Self {
+            Url::parse("https://example.com/path/to/other.pdf").unwrap()
+        }
+    }
+    impl Sealed for String {
+        fn example() -> Self {
+            "path/to/file.pdf".to_string()
+        }
+        fn example_destination() -> Self {
+            "path/to/other.pdf".to_string()
+        }
+    }
+}
+
+pub trait LocationType: sealed::Sealed + Serialize + DeserializeOwned + JsonSchema {}
+
+impl LocationType for Url {}
+impl LocationType for String {}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_list_request::<Location>())]
+pub struct ListRequest<Location: LocationType> {
+    pub location: Location,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "option_list_options"
+    )]
+    #[schemars(with = "Option<ListOptionsDef>")]
+    pub options: Option<opendal::options::ListOptions>,
+    /// Maximum number of entries returned in a single page.
+    ///
+    /// When set, the response contains a cursor for the next page if there are more entries.
+    ///
+    /// On stores without `start_after` support the cursor is positional: every page re-lists
+    /// from the start and skips the entries returned by previous pages. This relies on the store
+    /// listing entries in a stable order; entries added or removed between pages may be skipped
+    /// or returned twice.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
+    /// Cursor returned by a previous page to continue listing from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Only return entries matching the filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<ListFilter>,
+    /// Sort the returned entries.
+    ///
+    /// When paginating, entries are sorted within each page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<ListSort>,
+    /// Maximum number of entries returned (after filtering and sorting).
+    ///
+    /// Unlike `options.limit`, which is only a hint for the underlying service,
+    /// this caps the number of entries in the response.
+    ///
+    /// Cannot be combined with `pageSize`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entries: Option<usize>,
+    /// Fetch the full metadata of every file entry instead of relying on the listing.
+    ///
+    /// Filters on metadata are applied to the full metadata.
+    #[serde(default)]
+    pub stat: bool,
+    /// Maximum number of concurrent stat requests.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_CONCURRENCY`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stat_concurrency: Option<usize>,
+}
+
+pub(crate) fn example_list_request<Location: LocationType>() -> ListRequest<Location> {
+    ListRequest {
+        location: Location::example(),
+        options: None,
+        page_size: None,
+        cursor: None,
+        filter: None,
+        sort: None,
+        max_entries: None,
+        stat: false,
+        stat_concurrency: None,
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(example = example_list_response())]
+pub struct ListResponse {
+    /// Entries in the store.
+    pub entries: Vec<Entry>,
+    /// Cursor for the next page (if there are more entries).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+fn example_list_response() -> ListResponse {
+    ListResponse {
+        entries: vec![],
+        next_cursor: None,
+    }
+}
+
+pub(crate) async fn list<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: ListRequest<_L>,
+) -> Result<ListResponse, Error> {
+    if request.page_size.is_some() && request.max_entries.is_some() {
+        return Err(TerminalError::new_with_code(
+            400,
+            "maxEntries cannot be combined with pageSize",
+        )
+        .into());
+    }
+
+    let page_size = request.page_size.map(|size| size.max(1));
+    let mut options = request.options.unwrap_or_default();
+    let cursor = request.cursor.as_deref().map(decode_cursor).transpose()?;
+    let matcher = request
+        .filter
+        .map(|filter| filter.matcher(path))
+        .transpose()?;
+
+    // Stores without start_after support list everything from the start:
+    // skip the entries listed by previous pages instead.
+    // This assumes the listing order is stable between calls.
+    let mut skip = 0;
+
+    if let Some(cursor) = cursor {
+        if operator.info().full_capability().list_with_start_after {
+            options.start_after = Some(cursor.path);
+        } else {
+            skip = cursor.position;
+        }
+    }
+
+    let stat = request.stat;
+    let concurrency = request
+        .stat_concurrency
+        .unwrap_or(DEFAULT_BATCH_CONCURRENCY)
+        .max(1);
+
+    let op = operator.clone();
+    let mut lister = operator
+        .lister_options(path, options)
+        .await?
+        .enumerate()
+        .map(|(position, entry)| entry.map(|entry| (position, entry)))
+        .try_filter(|(position, entry)| {
+            let matched = matcher
+                .as_ref()
+                .is_none_or(|matcher| matcher.is_path_match(entry.path()));
+
+            futures::future::ready(*position >= skip && matched)
+        })
+        .map_ok(move |(position, entry)| {
+            let op = op.clone();
+
+            async move {
+                let (path, metadata) = entry.into_parts();
+
+                if stat && metadata.is_file() {
+                    let metadata = op.stat(&path).await?;
+
+                    return Ok((position, path, metadata));
+                }
+
+                Ok((position, path, metadata))
+            }
+        })
+        .try_buffered(concurrency);
+
+    let mut entries = Vec::new();
+    let mut next_cursor = None;
+    let mut last_position = 0;
+
+    while let Some((position, entry_path, metadata)) = lister.try_next().await? {
+        if let Some(matcher) = &matcher
+            && !matcher.is_metadata_match(&metadata)
+        {
+            continue;
+        }
+
+        if page_size.is_some_and(|size| entries.len() >= size) {
+            next_cursor = entries.last().map(|entry: &Entry| {
+                encode_cursor(&Cursor {
+                    position: last_position + 1,
+                    path: entry.path.clone(),
+                })
+            });
+
+            break;
+        }
+
+        last_position = position;
+        entries.push(Entry {
+            path: entry_path,
+            metadata: metadata.into(),
+        });
+
+        // Without sorting there is no need to look any further.
+        if request.sort.is_none()
+            && request
+                .max_entries
+                .is_some_and(|max_entries| entries.len() >= max_entries)
+        {
+            break;
+        }
+    }
+
+    if let Some(sort) = request.sort {
+        sort.apply(&mut entries);
+    }
+
+    if let Some(max_entries) = request.max_entries {
+        entries.truncate(max_entries);
+    }
+
+    Ok(ListResponse {
+        entries,
+        next_cursor,
+    })
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(example = example_list_delimited_response())]
+pub struct ListDelimitedResponse {
+    /// Files directly under the location.
+    pub files: Vec<Entry>,
+    /// Number of files returned.
+    pub file_count: usize,
+    /// Prefixes (directories) directly under the location.
+    pub prefixes: Vec<String>,
+    /// Number of prefixes returned.
+    pub prefix_count: usize,
+    /// Cursor for the next page (if there are more entries).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+fn example_list_delimited_response() -> ListDelimitedResponse {
+    ListDelimitedResponse {
+        files: vec![],
+        file_count: 0,
+        prefixes: vec!["path/to/dir/".to_string()],
+        prefix_count: 1,
+        next_cursor: None,
+    }
+}
+
+/// List a single level of a location, separating files from prefixes.
+pub(crate) async fn list_delimited<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: ListRequest<_L>,
+) -> Result<ListDelimitedResponse, Error> {
+    if request
+        .options
+        .as_ref()
+        .is_some_and(|options| options.recursive)
+    {
+        return Err(
+            TerminalError::new_with_code(400, "recursive listing cannot be delimited").into(),
+        );
+    }
+
+    let response = list(operator, path, request).await?;
+
+    let mut files = Vec::new();
+    let mut prefixes = Vec::new();
+
+    for entry in response.entries {
+        match entry.metadata.mode {
+            // The listed location itself is not a common prefix.
+            EntryMode::Dir if entry.path == path => (),
+            EntryMode::Dir => prefixes.push(entry.path),
+            _ => files.push(entry),
+        }
+    }
+
+    Ok(ListDelimitedResponse {
+        file_count: files.len(),
+        files,
+        prefix_count: prefixes.len(),
+        prefixes,
+        next_cursor: response.next_cursor,
+    })
+}
+
+/// Default depth of a tree listing.
+pub const DEFAULT_TREE_DEPTH: usize = 1;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_tree_request::<Location>())]
+pub struct TreeRequest<Location: LocationType> {
+    pub location: Location,
+    /// Number of directory levels to list under the location.
+    ///
+    /// Defaults to [`DEFAULT_TREE_DEPTH`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+}
+
+pub(crate) fn example_tree_request<Location: LocationType>() -> TreeRequest<Location> {
+    TreeRequest {
+        location: Location::example(),
+        depth: Some(3),
+    }
+}
+
+/// A node in a tree listing.
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeNode {
+    pub path: String,
+    pub name: String,
+    pub mode: EntryMode,
+    /// Size of the file, or the aggregate size of the files listed under the directory.
+    ///
+    /// Not present if the directory is deeper than the requested depth.
+    /// Files in such directories are not included in the size of their parents either.
+    ///
+    /// Sizes are taken from the listing metadata, so they may be missing for some stores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Number of files listed under the directory (1 for files).
+    ///
+    /// Not present if the directory is deeper than the requested depth.
+    /// Files in such directories are not included in the count of their parents either.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_count: Option<u64>,
+    /// Entries under the directory.
+    ///
+    /// Not present if the directory is deeper than the requested depth.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<TreeNode>>,
+}
+
+pub(crate) async fn tree<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: TreeRequest<_L>,
+) -> Result<TreeNode, Error> {
+    let depth = request.depth.unwrap_or(DEFAULT_TREE_DEPTH).max(1);
+
+    tree_dir(operator.clone(), path.to_string(), depth).await
+}
+
+fn tree_dir(
+    operator: opendal::Operator,
+    path: String,
+    depth: usize,
+) -> futures::future::BoxFuture<'static, Result<TreeNode, Error>> {
+    Box::pin(async move {
+        let mut node = TreeNode {
+            name: entry_name(&path),
+            path,
+            mode: EntryMode::Dir,
+            size: None,
+            file_count: None,
+            children: None,
+        };
+
+        if depth == 0 {
+            return Ok(node);
+        }
+
+        let mut size = 0;
+        let mut file_count = 0;
+        let mut children = Vec::new();
+
+        for entry in operator
+            .list_options(&node.path, opendal::options::ListOptions::default())
+            .await?
+        {
+            // The listed directory itself is part of the listing.
+            if entry.path() == node.path {
+                continue;
+            }
+
+            let (entry_path, metadata) = entry.into_parts();
+
+            let child = if metadata.is_dir() {
+                tree_dir(operator.clone(), entry_path, depth - 1).await?
+            } else {
+                TreeNode {
+                    name: entry_name(&entry_path),
+                    path: entry_path,
+                    mode: metadata.mode().into(),
+                    size: Some(metadata.content_length()),
+                    file_count: Some(1),
+                    children: None,
+                }
+            };
+
+            size += child.size.unwrap_or(0);
+            file_count += child.file_count.unwrap_or(0);
+            children.push(child);
+        }
+
+        node.size = Some(size);
+        node.file_count = Some(file_count);
+        node.children = Some(children);
+
+        Ok(node)
+    })
+}
+
+fn entry_name(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    let name = trimmed.rsplit('/').next().unwrap_or(trimmed);
+
+    if path.ends_with('/') && !name.is_empty() {
+        format!("{name}/")
+    } else {
+        name.to_string()
+    }
+}
+
+/// Default number of files examined by a single search page.
+pub const DEFAULT_SEARCH_PAGE_SIZE: usize = 1000;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_search_request::<Location>())]
+pub struct SearchRequest<Location: LocationType> {
+    pub location: Location,
+    /// Predicates on user metadata that every returned entry must match.
+    pub predicates: Vec<MetadataPredicate>,
+    /// Maximum number of files examined in a single page.
+    ///
+    /// A page may contain fewer matches (or none) even if there are more,
+    /// so keep searching as long as a cursor is returned.
+    ///
+    /// Defaults to [`DEFAULT_SEARCH_PAGE_SIZE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
+    /// Cursor returned by a previous page to continue searching from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// Maximum number of concurrent stat requests.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_CONCURRENCY`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+}
+
+pub(crate) fn example_search_request<Location: LocationType>() -> SearchRequest<Location> {
+    SearchRequest {
+        location: Location::example(),
+        predicates: vec![MetadataPredicate {
+            key: "tenant".to_string(),
+            value: Some("acme".to_string()),
+        }],
+        page_size: None,
+        cursor: None,
+        concurrency: None,
+    }
+}
+
+/// Predicate on a user metadata entry.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataPredicate {
+    /// Metadata key (case-insensitive).
+    pub key: String,
+    /// Value the metadata entry must be equal to.
+    ///
+    /// If omitted, the key only has to be present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl MetadataPredicate {
+    fn is_match(&self, user_metadata: &HashMap<String, String>) -> bool {
+        user_metadata.iter().any(|(key, value)| {
+            key.eq_ignore_ascii_case(&self.key)
+                && self.value.as_ref().is_none_or(|expected| value == expected)
+        })
+    }
+}
+
+pub(crate) async fn search<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: SearchRequest<_L>,
+) -> Result<ListResponse, Error> {
+    let response = list(
+        operator,
+        path,
+        ListRequest {
+            location: request.location,
+            options: Some(opendal::options::ListOptions {
+                recursive: true,
+                ..Default::default()
+            }),
+            page_size: Some(request.page_size.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE)),
+            cursor: request.cursor,
+            filter: Some(ListFilter {
+                modes: vec![EntryMode::File],
+                ..Default::default()
+            }),
+            sort: None,
+            max_entries: None,
+            stat: true,
+            stat_concurrency: request.concurrency,
+        },
+    )
+    .await?;
+
+    let entries = response
+        .entries
+        .into_iter()
+        .filter(|entry| {
+            let user_metadata = entry.metadata.user_metadata.as_ref();
+
+            request.predicates.iter().all(|predicate| {
+                user_metadata.is_some_and(|user_metadata| predicate.is_match(user_metadata))
+            })
+        })
+        .collect();
+
+    Ok(ListResponse {
+        entries,
+        next_cursor: response.next_cursor,
+    })
+}
+
+/// Position of the last entry returned by a list page.
+#[derive(Debug, PartialEq, Eq)]
+struct Cursor {
+    /// Number of entries listed up to (and including) the last returned entry.
+    position: usize,
+    /// Path of the last returned entry.
+    path: String,
+}
+
+/// Encode the position of the last listed entry into an opaque cursor.
+fn encode_cursor(cursor: &Cursor) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(format!("{}:{}", cursor.position, cursor.path))
+}
+
+fn decode_cursor(cursor: &str) -> Result<Cursor, Error> {
+    BASE64_URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|cursor| String::from_utf8(cursor).ok())
+        .and_then(|cursor| {
+            let (position, path) = cursor.split_once(':')?;
+
+            Some(Cursor {
+                position: position.parse().ok()?,
+                path: path.to_string(),
+            })
+        })
+        .ok_or_else(|| TerminalError::new_with_code(400, "invalid cursor").into())
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_usage_request::<Location>())]
+pub struct UsageRequest<Location: LocationType> {
+    pub location: Location,
+    /// Fetch the full metadata of every file instead of relying on the listing.
+    ///
+    /// Needed for stores that do not return content types in listings (eg. S3).
+    /// Files listed without a content length are always looked up.
+    #[serde(default)]
+    pub stat: bool,
+}
+
+pub(crate) fn example_usage_request<Location: LocationType>() -> UsageRequest<Location> {
+    UsageRequest {
+        location: Location::example(),
+        stat: false,
+    }
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageResponse {
+    /// Total size of all files in bytes.
+    pub total_bytes: u64,
+    /// Number of files.
+    pub objects: u64,
+    /// Number of directories reported by the store.
+    pub directories: u64,
+    /// Usage grouped by content type.
+    ///
+    /// Files without a known content type are grouped under `unknown`.
+    pub by_content_type: BTreeMap<String, UsageSummary>,
+    /// Usage grouped by top-level prefix under the location.
+    ///
+    /// Files directly under the location are grouped under the location itself.
+    pub by_prefix: BTreeMap<String, UsageSummary>,
+}
+
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSummary {
+    pub bytes: u64,
+    pub objects: u64,
+}
+
+impl UsageSummary {
+    fn add(&mut self, bytes: u64) {
+        self.bytes += bytes;
+        self.objects += 1;
+    }
+}
+
+pub(crate) async fn usage<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: UsageRequest<_L>,
+) -> Result<UsageResponse, Error> {
+    let options = opendal::options::ListOptions {
+        recursive: true,
+        ..Default::default()
+    };
+
+    let lister = operator.lister_options(path, options).await?;
+
+    let op = operator.clone();
+    let mut entries = lister
+        .map_ok(move |entry| {
+            let op = op.clone();
+            let stat = entry.metadata().is_file()
+                && (request.stat || entry.metadata().content_length() == 0);
+
+            async move {
+                let (path, metadata) = entry.into_parts();
+
+                if stat {
+                    let metadata = op.stat(&path).await?;
+
+                    return Ok((path, metadata));
+                }
+
+                Ok((path, metadata))
+            }
+        })
+        .try_buffered(DEFAULT_BATCH_CONCURRENCY);
+
+    let mut usage = UsageResponse::default();
+
+    while let Some((entry_path, metadata)) = entries.try_next().await? {
+        // The listed location itself is not part of its usage.
+        if entry_path == path {
+            continue;
+        }
+
+        if metadata.is_dir() {
+            usage.directories += 1;
+
+            continue;
+        }
+
+        let size = metadata.content_length();
+        let content_type = metadata.content_type().unwrap_or("unknown").to_string();
+
+        let relative = entry_path.strip_prefix(path).unwrap_or(&entry_path);
+        let prefix = match relative.find('/') {
+            Some(i) => format!("{path}{}", &relative[..=i]),
+            None => path.to_string(),
+        };
+
+        usage.total_bytes += size;
+        usage.objects += 1;
+        usage
+            .by_content_type
+            .entry(content_type)
+            .or_default()
+            .add(size);
+        usage.by_prefix.entry(prefix).or_default().add(size);
+    }
+
+    Ok(usage)
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_stat_request::<Location>())]
+pub struct StatRequest<Location: LocationType> {
+    pub location: Location,
+    /// Options for the stat operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<StatOptions>,
+}
+
+pub(crate) fn example_stat_request<Location: LocationType>() -> StatRequest<Location> {
+    StatRequest {
+        location: Location::example(),
+        options: None,
+    }
+}
+
+pub(crate) async fn stat<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: StatRequest<_L>,
+) -> Result<Metadata, Error> {
+    let metadata = match request.options {
+        Some(options) => operator.stat_options(path, options.into()).await?,
+        None => operator.stat(path).await?,
+    };
+
+    Ok(metadata.into())
+}
+
+/// Default maximum number of bytes returned inline by a read operation.
+pub const DEFAULT_MAX_READ_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_read_request::<Location>())]
+pub struct ReadRequest<Location: LocationType> {
+    pub location: Location,
+    /// Encoding of the returned content.
+    #[serde(default)]
+    pub encoding: Encoding,
+    /// Maximum number of bytes that can be returned inline.
+    ///
+    /// Reading more than this fails with a terminal error.
+    /// Defaults to [`DEFAULT_MAX_READ_SIZE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<u64>,
+    /// Options for the read operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<ReadOptions>,
+}
+
+pub(crate) fn example_read_request<Location: LocationType>() -> ReadRequest<Location> {
+    ReadRequest {
+        location: Location::example(),
+        encoding: Encoding::Text,
+        max_size: None,
+        options: None,
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(example = example_read_response())]
+pub struct ReadResponse {
+    /// Content read from the store.
+    pub content: String,
+    /// Encoding of the content.
+    pub encoding: Encoding,
+    /// Metadata of the object that was read.
+    pub metadata: Metadata,
+}
+
+fn example_read_response() -> ReadResponse {
+    ReadResponse {
+        content: "Hello, World!".to_string(),
+        encoding: Encoding::Text,
+        metadata: Metadata::default(),
+    }
+}
+
+pub(crate) async fn read<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: ReadRequest<_L>,
+) -> Result<ReadResponse, Error> {
+    let max_size = request.max_size.unwrap_or(DEFAULT_MAX_READ_SIZE);
+    let options: opendal::options::ReadOptions = request.options.unwrap_or_default().into();
+
+    let metadata = operator
+        .stat_options(
+            path,
+            opendal::options::StatOptions {
+                version: options.version.clone(),
+                if_match: options.if_match.clone(),
+                if_none_match: options.if_none_match.clone(),
+                if_modified_since: options.if_modified_since,
+                if_unmodified_since: options.if_unmodified_since,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let range = ContentRange::new(
+        metadata.content_length(),
+        options.range.offset(),
+        options.range.size(),
+    );
+
+    if let Some(size) = range.size()
+        && size > max_size
+    {
+        return Err(payload_too_large(size, max_size));
+    }
+
+    let mut content = Vec::new();
+
+    if !range.is_empty() {
+        let mut stream = operator
+            .reader_options(
+                path,
+                opendal::options::ReaderOptions {
+                    version: options.version,
+                    if_match: options.if_match,
+                    if_none_match: options.if_none_match,
+                    if_modified_since: options.if_modified_since,
+                    if_unmodified_since: options.if_unmodified_since,
+                    concurrent: options.concurrent,
+                    chunk: options.chunk,
+                    gap: options.gap,
+                    ..Default::default()
+                },
+            )
+            .await?
+            .into_bytes_stream(range.bounds())
+            .await?;
+
+        while let Some(chunk) = stream.try_next().await.map_err(read_error)? {
+            content.extend_from_slice(range.within(&chunk, content.len() as u64));
+
+            // The object may have changed between stat and read (or its size was not reported).
+            if content.len() as u64 > max_size {
+                return Err(payload_too_large(content.len() as u64, max_size));
+            }
+
+            if range.is_complete(content.len() as u64) {
+                break;
+            }
+        }
+    }
+
+    Ok(ReadResponse {
+        content: request.encoding.encode(content)?,
+        encoding: request.encoding,
+        metadata: metadata.into(),
+    })
+}
+
+/// Range of content to read, bounded by the content length reported by the store.
+///
+/// Some stores do not report content lengths (in which case it is 0):
+/// their content is read until the requested size or the end of the content instead.
+struct ContentRange {
+    offset: u64,
+    /// End of the range, if the content length is known.
+    end: Option<u64>,
+    /// Maximum number of bytes to read.
+    limit: Option<u64>,
+}
+
+impl ContentRange {
+    fn new(length: u64, offset: u64, size: Option<u64>) -> Self {
+        if length == 0 {
+            return ContentRange {
+                offset,
+                end: None,
+                limit: size,
+            };
+        }
+
+        // Never request more than what the stat reported.
+        let end = size.map_or(length, |size| offset.saturating_add(size).min(length));
+
+        ContentRange {
+            offset,
+            end: Some(end),
+            limit: Some(end.saturating_sub(offset)),
+        }
+    }
+
+    /// Number of bytes in the range, if the content length is known.
+    fn size(&self) -> Option<u64> {
+        self.end.map(|end| end.saturating_sub(self.offset))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.limit == Some(0)
+    }
+
+    fn bounds(&self) -> (Bound<u64>, Bound<u64>) {
+        (
+            Bound::Included(self.offset),
+            self.end.map_or(Bound::Unbounded, Bound::Excluded),
+        )
+    }
+
+    /// Part of a chunk within the range, given the number of bytes read before it.
+    fn within<'a>(&self, chunk: &'a [u8], read: u64) -> &'a [u8] {
+        match self.limit {
+            Some(limit) => {
+                let remaining = usize::try_from(limit.saturating_sub(read)).unwrap_or(usize::MAX);
+
+                &chunk[..chunk.len().min(remaining)]
+            }
+            None => chunk,
+        }
+    }
+
+    fn is_complete(&self, read: u64) -> bool {
+        self.limit.is_some_and(|limit| read >= limit)
+    }
+}
+
+fn payload_too_large(size: u64, max_size: u64) -> Error {
+    TerminalError::new_with_code(
+        413,
+        format!("content size {size} exceeds the maximum inline size of {max_size} bytes"),
+    )
+    .into()
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_write_request::<Location>())]
+pub struct WriteRequest<Location: LocationType> {
+    pub location: Location,
+    /// Content to write.
+    pub content: String,
+    /// Encoding of the content.
+    #[serde(default)]
+    pub encoding: Encoding,
+    /// Options for the write operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<WriteOptions>,
+}
+
+pub(crate) fn example_write_request<Location: LocationType>() -> WriteRequest<Location> {
+    WriteRequest {
+        location: Location::example(),
+        content: "Hello, World!".to_string(),
+        encoding: Encoding::Text,
+        options: Some(WriteOptions {
+            content_type: Some("text/plain".to_string()),
+            ..Default::default()
+        }),
+    }
+}
+
+pub(crate) async fn write<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: WriteRequest<_L>,
+) -> Result<Metadata, Error> {
+    let content = request.encoding.decode(request.content)?;
+
+    let metadata = match request.options {
+        Some(options) => {
+            operator
+                .write_options(path, content, options.into())
+                .await?
+        }
+        None => operator.write(path, content).await?,
+    };
+
+    Ok(metadata.into())
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_append_request::<Location>())]
+pub struct AppendRequest<Location: LocationType> {
+    pub location: Location,
+    /// Content to append.
+    pub content: String,
+    /// Encoding of the content.
+    #[serde(default)]
+    pub encoding: Encoding,
+    /// Options for the append operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<AppendOptions>,
+}
+
+pub(crate) fn example_append_request<Location: LocationType>() -> AppendRequest<Location> {
+    AppendRequest {
+        location: Location::example(),
+        content: "Hello, World!\n".to_string(),
+        encoding: Encoding::Text,
+        options: None,
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppendResponse {
+    /// Length of the object after appending.
+    pub content_length: u64,
+}
+
+pub(crate) async fn append<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: AppendRequest<_L>,
+) -> Result<(), Error> {
+    if !operator.info().full_capability().write_can_append {
+        return Err(TerminalError::new_with_code(
+            501,
+            format!("{} does not support append", operator.info().scheme()),
+        )
+        .into());
+    }
+
+    let content = request.encoding.decode(request.content)?;
+
+    operator
+        .write_options(path, content, request.options.unwrap_or_default().into())
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_delete_request::<Location>())]
+pub struct DeleteRequest<Location: LocationType> {
+    pub location: Location,
+    /// Options for the delete operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<DeleteOptions>,
+}
+
+pub(crate) fn example_delete_request<Location: LocationType>() -> DeleteRequest<Location> {
+    DeleteRequest {
+        location: Location::example(),
+        options: None,
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(example = example_delete_response())]
+pub struct DeleteResponse {
+    /// Number of deleted entries.
+    pub deleted: u64,
+}
+
+fn example_delete_response() -> DeleteResponse {
+    DeleteResponse { deleted: 1 }
+}
+
+pub(crate) async fn delete<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: DeleteRequest<_L>,
+) -> Result<DeleteResponse, Error> {
+    let options: opendal::options::DeleteOptions = request.options.unwrap_or_default().into();
+
+    // Delete is idempotent, so check whether there is anything to delete to report an accurate count.
+    let stat = operator
+        .stat_options(
+            path,
+            opendal::options::StatOptions {
+                version: options.version.clone(),
+                ..Default::default()
+            },
+        )
+        .await;
+
+    match stat {
+        Ok(_) => (),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => {
+            return Ok(DeleteResponse { deleted: 0 });
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    operator.delete_options(path, options).await?;
+
+    Ok(DeleteResponse { deleted: 1 })
+}
+
+/// Default number of entries removed in a single step of a recursive delete.
+pub const DEFAULT_REMOVE_ALL_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_remove_all_request::<Location>())]
+pub struct RemoveAllRequest<Location: LocationType> {
+    /// Directory to remove recursively.
+    ///
+    /// The root of the store cannot be removed.
+    pub location: Location,
+    /// Maximum number of entries removed in a single (journaled) step.
+    ///
+    /// Defaults to [`DEFAULT_REMOVE_ALL_BATCH_SIZE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<usize>,
+}
+
+impl<Location: LocationType> RemoveAllRequest<Location> {
+    pub(crate) fn batch_size(&self) -> usize {
+        self.batch_size
+            .unwrap_or(DEFAULT_REMOVE_ALL_BATCH_SIZE)
+            .max(1)
+    }
+}
+
+pub(crate) fn example_remove_all_request<Location: LocationType>() -> RemoveAllRequest<Location> {
+    RemoveAllRequest {
+        location: Location::example(),
+        batch_size: None,
+    }
+}
+
+/// Remove a single batch of entries under the given path.
+///
+/// Callers are expected to call this repeatedly until fewer entries than the batch size are removed.
+/// Since removed entries are no longer listed, each call picks up where the previous one left off.
+pub(crate) async fn remove_all<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: RemoveAllRequest<_L>,
+) -> Result<DeleteResponse, Error> {
+    let path = path.trim_matches('/');
+
+    if path.is_empty() {
+        return Err(
+            TerminalError::new_with_code(400, "the root of the store cannot be removed").into(),
+        );
+    }
+
+    // Without a trailing slash, the listing would also match siblings sharing the same prefix.
+    let path = format!("{path}/");
+
+    let entries: Vec<opendal::Entry> = operator
+        .lister_options(
+            &path,
+            opendal::options::ListOptions {
+                recursive: true,
+                ..Default::default()
+            },
+        )
+        .await?
+        .take(request.batch_size())
+        .try_collect()
+        .await?;
+
+    let deleted = entries.len() as u64;
+
+    if deleted > 0 {
+        operator.delete_iter(entries).await?;
+    }
+
+    Ok(DeleteResponse { deleted })
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_batch_request::<Location>())]
+pub struct BatchRequest<Location: LocationType, Options: DeserializeOwned + JsonSchema> {
+    /// Locations to process.
+    pub locations: Vec<Location>,
+    /// Options for the batch operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Options>,
+}
+
+pub(crate) fn example_batch_request<Location: LocationType>() -> BatchRequest<Location, ()> {
+    BatchRequest {
+        locations: vec![Location::example()],
+        options: None,
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType, Result: DeserializeOwned"))]
+#[schemars(bound = "Location: LocationType, Result: JsonSchema")]
+pub struct BatchResponse<Location: LocationType, Result> {
+    /// Results in the same order as the requested locations.
+    pub results: Vec<BatchResult<Location, Result>>,
+}
+
+impl<Location: LocationType, Result> BatchResponse<Location, Result> {
+    pub(crate) fn new(locations: Vec<Location>, results: Vec<Result>) -> Self {
+        Self {
+            results: locations
+                .into_iter()
+                .zip(results)
+                .map(|(location, result)| BatchResult { location, result })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType, Result: DeserializeOwned"))]
+#[schemars(bound = "Location: LocationType, Result: JsonSchema")]
+pub struct BatchResult<Location: LocationType, Result> {
+    pub location: Location,
+    #[serde(flatten)]
+    pub result: Result,
+}
+
+/// Default number of concurrent operations performed by batch handlers.
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 16;
+
+/// Options for batch delete operations.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteBatchOptions {
+    /// Check whether each location exists before deleting it.
+    ///
+    /// Deleting is idempotent in most stores, so missing locations are only reported as
+    /// `notFound` when this is enabled. Requires an extra stat call per location.
+    #[serde(default)]
+    pub check_exists: bool,
+    /// Maximum number of concurrent requests made for individual locations.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_CONCURRENCY`].
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteResult {
+    pub status: DeleteStatus,
+    /// Error that occurred while deleting the location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetails>,
+}
+
+impl DeleteResult {
+    fn status(status: DeleteStatus) -> Self {
+        Self {
+            status,
+            error: None,
+        }
+    }
+
+    fn error(err: opendal::Error) -> Self {
+        Self {
+            status: DeleteStatus::Error,
+            error: Some(err.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum DeleteStatus {
+    /// The location was deleted.
+    Deleted,
+    /// The location did not exist.
+    NotFound,
+    /// The location could not be deleted.
+    Error,
+}
+
+pub(crate) async fn delete_batch(
+    operator: &opendal::Operator,
+    paths: Vec<String>,
+    options: Option<DeleteBatchOptions>,
+) -> Result<Vec<DeleteResult>, Error> {
+    let options = options.unwrap_or_default();
+    let concurrency = options
+        .concurrency
+        .unwrap_or(DEFAULT_BATCH_CONCURRENCY)
+        .max(1);
+
+    let mut results: Vec<Option<DeleteResult>> = vec![None; paths.len()];
+
+    if options.check_exists {
+        let stats: Vec<_> = futures::stream::iter(paths.clone())
+            .map(|path| {
+                let operator = operator.clone();
+
+                async move { operator.stat(&path).await }
+            })
+            .buffered(concurrency)
+            .collect()
+            .await;
+
+        for (result, stat) in results.iter_mut().zip(stats) {
+            match stat {
+                Ok(_) => (),
+                Err(err) if err.kind() == opendal::ErrorKind::NotFound => {
+                    *result = Some(DeleteResult::status(DeleteStatus::NotFound));
+                }
+                Err(err) => *result = Some(DeleteResult::error(err)),
+            }
+        }
+    }
+
+    let pending: Vec<(usize, String)> = paths
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| results[*i].is_none())
+        .collect();
+
+    // The deleter batches deletes where the store supports it,
+    // but it cannot tell which path failed, so fall back to deleting one by one on error.
+    let pending_paths: Vec<String> = pending.iter().map(|(_, path)| path.clone()).collect();
+
+    let mut deleter = operator.deleter().await?;
+    let batch = match deleter.delete_iter(pending_paths).await {
+        Ok(()) => deleter.close().await,
+        Err(err) => Err(err),
+    };
+
+    if batch.is_ok() {
+        for (i, _) in pending {
+            results[i] = Some(DeleteResult::status(DeleteStatus::Deleted));
+        }
+    } else {
+        let deletes: Vec<_> = futures::stream::iter(pending)
+            .map(|(i, path)| {
+                let operator = operator.clone();
+
+                async move { (i, operator.delete(&path).await) }
+            })
+            .buffered(concurrency)
+            .collect()
+            .await;
+
+        for (i, delete) in deletes {
+            results[i] = Some(match delete {
+                Ok(()) => DeleteResult::status(DeleteStatus::Deleted),
+                Err(err) => DeleteResult::error(err),
+            });
+        }
+    }
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Options for batch stat operations.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatBatchOptions {
+    /// Maximum number of concurrent stat requests.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_CONCURRENCY`].
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatResult {
+    /// Whether the location exists.
+    ///
+    /// `false` if the existence could not be determined due to an error.
+    pub exists: bool,
+    /// Metadata of the location (if it exists).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+    /// Error that occurred while retrieving metadata of the location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetails>,
+}
+
+impl From<opendal::Result<opendal::Metadata>> for StatResult {
+    fn from(result: opendal::Result<opendal::Metadata>) -> Self {
+        match result {
+            Ok(metadata) => Self {
+                exists: true,
+                metadata: Some(metadata.into()),
+                error: None,
+            },
+            Err(err) if err.kind() == opendal::ErrorKind::NotFound => Self {
+                exists: false,
+                metadata: None,
+                error: None,
+            },
+            Err(err) => Self {
+                exists: false,
+                metadata: None,
+                error: Some(err.into()),
+            },
+        }
+    }
+}
+
+pub(crate) async fn stat_batch(
+    operator: &opendal::Operator,
+    paths: Vec<String>,
+    options: Option<StatBatchOptions>,
+) -> Result<Vec<StatResult>, Error> {
+    let concurrency = options
+        .unwrap_or_default()
+        .concurrency
+        .unwrap_or(DEFAULT_BATCH_CONCURRENCY)
+        .max(1);
+
+    let results = futures::stream::iter(paths)
+        .map(|path| {
+            let operator = operator.clone();
+
+            async move { operator.stat(&path).await.into() }
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    Ok(results)
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_copy_request::<Location>())]
+pub struct CopyRequest<Location: LocationType> {
+    pub source: Location,
+    pub destination: Location,
+    /// Options for the copy operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<CopyOptions>,
+}
+
+pub(crate) fn example_copy_request<Location: LocationType>() -> CopyRequest<Location> {
+    CopyRequest {
+        source: Location::example(),
+        destination: Location::example_destination(),
+        options: None,
+    }
+}
+
+/// Copy a file using the native copy operation of the store.
+///
+/// Falls back to streaming the content through the service when the source and destination
+/// are in different stores or the store doesn't support copying natively.
+pub(crate) async fn copy<_L: LocationType>(
+    source: &opendal::Operator,
+    source_path: &str,
+    destination: &opendal::Operator,
+    destination_path: &str,
+    same_store: bool,
+    request: CopyRequest<_L>,
+) -> Result<(), Error> {
+    let mut options = request.options.unwrap_or_default();
+
+    let capability = source.info().full_capability();
+
+    if same_store && capability.copy {
+        // Stores that cannot copy conditionally check for the destination first (which is racy).
+        if options.if_not_exists && !capability.copy_with_if_not_exists {
+            if destination.exists(destination_path).await? {
+                return Err(already_exists(destination_path));
+            }
+
+            options.if_not_exists = false;
+        }
+
+        source
+            .copy_options(source_path, destination_path, options)
+            .await?;
+
+        return Ok(());
+    }
+
+    if options.if_not_exists {
+        if destination
+            .info()
+            .full_capability()
+            .write_with_if_not_exists
+        {
+            let metadata = source.stat(source_path).await?;
+
+            return copy_file(
+                source,
+                source_path,
+                destination,
+                destination_path,
+                opendal::options::WriteOptions {
+                    content_type: metadata.content_type().map(|s| s.to_string()),
+                    if_not_exists: true,
+                    ..Default::default()
+                },
+            )
+            .await;
+        }
+
+        if destination.exists(destination_path).await? {
+            return Err(already_exists(destination_path));
+        }
+    }
+
+    Copier::new(source.clone(), destination.clone())
+        .copy_options(
+            source_path,
+            destination_path,
+            opendal_util::CopyOptions {
+                disable_glob: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Stream a single file from one store to another.
+pub(crate) async fn copy_file(
+    src_op: &opendal::Operator,
+    src_path: &str,
+    dst_op: &opendal::Operator,
+    dst_path: &str,
+    options: opendal::options::WriteOptions,
+) -> Result<(), Error> {
+    let if_not_exists = options.if_not_exists;
+
+    // Conditional writes fail with a precondition error on most stores.
+    let write_error = |err: opendal::Error| match err.kind() {
+        opendal::ErrorKind::ConditionNotMatch if if_not_exists => already_exists(dst_path),
+        _ => err.into(),
+    };
+
+    let mut stream = src_op.reader(src_path).await?.into_bytes_stream(..).await?;
+    let mut writer = dst_op
+        .writer_options(dst_path, options)
+        .await
+        .map_err(write_error)?;
+
+    while let Some(chunk) = stream.try_next().await.map_err(read_error)? {
+        writer.write(chunk).await.map_err(write_error)?;
+    }
+
+    writer.close().await.map_err(write_error)?;
+
+    Ok(())
+}
+
+/// Recover the store error behind a read error, so it is reported with the right code.
+fn read_error(err: std::io::Error) -> Error {
+    match err.downcast::<opendal::Error>() {
+        Ok(err) => err.into(),
+        Err(err) => err.into(),
+    }
+}
+
+pub(crate) fn already_exists(path: &str) -> Error {
+    TerminalError::new_with_code(409, format!("destination {path} already exists")).into()
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_rename_request::<Location>())]
+pub struct RenameRequest<Location: LocationType> {
+    pub source: Location,
+    pub destination: Location,
+}
+
+pub(crate) fn example_rename_request<Location: LocationType>() -> RenameRequest<Location> {
+    RenameRequest {
+        source: Location::example(),
+        destination: Location::example_destination(),
+    }
+}
+
+/// Rename a file using the native rename operation of the store.
+pub(crate) async fn rename<_L: LocationType>(
+    source: &opendal::Operator,
+    source_path: &str,
+    _destination: &opendal::Operator,
+    destination_path: &str,
+    same_store: bool,
+    _request: RenameRequest<_L>,
+) -> Result<(), Error> {
+    if !same_store {
+        return Err(TerminalError::new_with_code(
+            400,
+            "source and destination must be in the same store",
+        )
+        .into());
+    }
+
+    if !source.info().full_capability().rename {
+        return Err(TerminalError::new_with_code(
+            501,
+            format!("{} does not support rename", source.info().scheme()),
+        )
+        .into());
+    }
+
+    source.rename(source_path, destination_path).await?;
+
+    Ok(())
+}
+
+/// Size of the chunks written to the destination of an inventory export.
+const INVENTORY_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_export_inventory_request::<Location>())]
+pub struct ExportInventoryRequest<Location: LocationType> {
+    /// Location to list recursively.
+    pub source: Location,
+    /// Location to write the inventory to.
+    pub destination: Location,
+    /// Format of the inventory.
+    #[serde(default)]
+    pub format: InventoryFormat,
+    /// Fetch the full metadata of every file instead of relying on the listing.
+    ///
+    /// Needed for stores that do not return content types in listings (eg. S3),
+    /// otherwise the content type column of the inventory stays empty.
+    #[serde(default)]
+    pub stat: bool,
+}
+
+pub(crate) fn example_export_inventory_request<Location: LocationType>()
+-> ExportInventoryRequest<Location> {
+    ExportInventoryRequest {
+        source: Location::example(),
+        destination: Location::example_destination(),
+        format: InventoryFormat::Ndjson,
+        stat: false,
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportInventoryResponse {
+    /// Number of objects written to the inventory.
+    pub count: u64,
+    /// Metadata of the written inventory.
+    pub metadata: Metadata,
+}
+
+/// Format of an inventory export.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum InventoryFormat {
+    /// One JSON object per line.
+    #[default]
+    Ndjson,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+impl InventoryFormat {
+    fn content_type(self) -> &'static str {
+        match self {
+            InventoryFormat::Ndjson => "application/x-ndjson",
+            InventoryFormat::Csv => "text/csv",
+        }
     }
-    impl Sealed for String {
-        fn example() -> Self {
-            "path/to/file.pdf".to_string()
+
+    fn header(self) -> Option<&'static str> {
+        match self {
+            InventoryFormat::Ndjson => None,
+            InventoryFormat::Csv => Some("path,size,etag,lastModified,contentType\n"),
+        }
+    }
+
+    fn encode(self, record: &InventoryRecord, buf: &mut Vec<u8>) -> Result<(), Error> {
+        match self {
+            InventoryFormat::Ndjson => {
+                serde_json::to_writer(&mut *buf, record).map_err(anyhow::Error::from)?;
+            }
+            InventoryFormat::Csv => {
+                let fields = [
+                    csv_field(record.path),
+                    record.size.to_string(),
+                    csv_field(record.etag.unwrap_or_default()),
+                    record
+                        .last_modified
+                        .map(|t| t.to_string())
+                        .unwrap_or_default(),
+                    csv_field(record.content_type.unwrap_or_default()),
+                ];
+
+                buf.extend_from_slice(fields.join(",").as_bytes());
+            }
         }
+
+        buf.push(b'\n');
+
+        Ok(())
     }
 }
 
-pub trait LocationType: sealed::Sealed + Serialize + DeserializeOwned + JsonSchema {}
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InventoryRecord<'a> {
+    path: &'a str,
+    size: u64,
+    etag: Option<&'a str>,
+    last_modified: Option<jiff::Timestamp>,
+    content_type: Option<&'a str>,
+}
 
-impl LocationType for Url {}
-impl LocationType for String {}
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub(crate) async fn export_inventory<_L: LocationType>(
+    source: &opendal::Operator,
+    source_path: &str,
+    destination: &opendal::Operator,
+    destination_path: &str,
+    same_store: bool,
+    request: ExportInventoryRequest<_L>,
+) -> Result<ExportInventoryResponse, Error> {
+    let format = request.format;
+
+    let lister = source
+        .lister_options(
+            source_path,
+            opendal::options::ListOptions {
+                recursive: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let mut writer = destination
+        .writer_options(
+            destination_path,
+            opendal::options::WriteOptions {
+                content_type: Some(format.content_type().to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    // Exporting the inventory into the listed location should not list the inventory itself.
+    let destination_path = destination_path.to_string();
+
+    let op = source.clone();
+    let mut entries = lister
+        .try_filter(move |entry| {
+            futures::future::ready(
+                entry.metadata().is_file() && !(same_store && entry.path() == destination_path),
+            )
+        })
+        .map_ok(move |entry| {
+            let op = op.clone();
+            let stat = request.stat || entry.metadata().content_length() == 0;
+
+            async move {
+                let (path, metadata) = entry.into_parts();
+
+                if stat {
+                    let metadata = op.stat(&path).await?;
+
+                    return Ok((path, metadata));
+                }
+
+                Ok((path, metadata))
+            }
+        })
+        .try_buffered(DEFAULT_BATCH_CONCURRENCY);
+
+    let written = async {
+        let mut buf = Vec::with_capacity(INVENTORY_CHUNK_SIZE);
+        let mut count = 0;
+
+        if let Some(header) = format.header() {
+            buf.extend_from_slice(header.as_bytes());
+        }
+
+        while let Some((path, metadata)) = entries.try_next().await? {
+            let record = InventoryRecord {
+                path: &path,
+                size: metadata.content_length(),
+                etag: metadata.etag(),
+                last_modified: metadata.last_modified().map(|t| t.into_inner()),
+                content_type: metadata.content_type(),
+            };
+
+            format.encode(&record, &mut buf)?;
+            count += 1;
+
+            if buf.len() >= INVENTORY_CHUNK_SIZE {
+                writer
+                    .write(std::mem::replace(
+                        &mut buf,
+                        Vec::with_capacity(INVENTORY_CHUNK_SIZE),
+                    ))
+                    .await?;
+            }
+        }
+
+        if !buf.is_empty() {
+            writer.write(buf).await?;
+        }
+
+        Ok::<_, Error>(count)
+    }
+    .await;
+
+    let count = match written {
+        Ok(count) => count,
+        Err(err) => {
+            // Do not leave a partial inventory behind.
+            let _ = writer.abort().await;
+
+            return Err(err);
+        }
+    };
+
+    let metadata = writer.close().await?;
+
+    Ok(ExportInventoryResponse {
+        count,
+        metadata: metadata.into(),
+    })
+}
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(bound(deserialize = "Location: LocationType"))]
-#[schemars(bound = "Location: LocationType", example = example_list_request::<Location>())]
-pub struct ListRequest<Location: LocationType> {
+#[schemars(bound = "Location: LocationType", example = example_checksum_request::<Location>())]
+pub struct ChecksumRequest<Location: LocationType> {
     pub location: Location,
-    #[serde(
-        default,
-        skip_serializing_if = "Option::is_none",
-        with = "option_list_options"
-    )]
-    #[schemars(with = "Option<ListOptionsDef>")]
-    pub options: Option<opendal::options::ListOptions>,
+    /// Digests to compute.
+    ///
+    /// Defaults to SHA-256.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub algorithms: Vec<ChecksumAlgorithm>,
+    /// Only compute digests over the given range of the content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<BytesRange>,
 }
 
-pub(crate) fn example_list_request<Location: LocationType>() -> ListRequest<Location> {
-    ListRequest {
+pub(crate) fn example_checksum_request<Location: LocationType>() -> ChecksumRequest<Location> {
+    ChecksumRequest {
         location: Location::example(),
-        options: None,
+        algorithms: vec![ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Md5],
+        range: None,
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+/// Digests of an object, hex encoded.
+///
+/// Only the requested digests are present.
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-#[schemars(example = example_list_response())]
-pub struct ListResponse {
-    /// Entries in the store.
-    pub entries: Vec<Entry>,
+pub struct ChecksumResponse {
+    /// Number of bytes digested.
+    pub size: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
+    /// CRC32C checksum as a big-endian hex string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crc32c: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blake3: Option<String>,
 }
 
-fn example_list_response() -> ListResponse {
-    ListResponse { entries: vec![] }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    Sha256,
+    Sha1,
+    Md5,
+    Crc32c,
+    Blake3,
 }
 
-pub(crate) async fn list<_L: LocationType>(
+pub(crate) async fn checksum<_L: LocationType>(
     operator: &opendal::Operator,
     path: &str,
-    request: ListRequest<_L>,
-) -> Result<ListResponse, Error> {
-    let entries = match request.options {
-        Some(options) => operator.list_options(path, options).await?,
-        None => operator.list(path).await?,
-    };
+    request: ChecksumRequest<_L>,
+) -> Result<ChecksumResponse, Error> {
+    let mut algorithms = request.algorithms;
+    if algorithms.is_empty() {
+        algorithms.push(ChecksumAlgorithm::Sha256);
+    }
 
-    Ok(ListResponse {
-        entries: entries.into_iter().map(Into::into).collect(),
-    })
+    let range = request.range.unwrap_or_default();
+    let metadata = operator.stat(path).await?;
+    let range = ContentRange::new(metadata.content_length(), range.offset, range.size);
+
+    let mut hasher = Hasher::new(&algorithms);
+
+    if !range.is_empty() {
+        let mut stream = operator
+            .reader(path)
+            .await?
+            .into_bytes_stream(range.bounds())
+            .await?;
+
+        while let Some(chunk) = stream.try_next().await.map_err(read_error)? {
+            hasher.update(range.within(&chunk, hasher.size));
+
+            if range.is_complete(hasher.size) {
+                break;
+            }
+        }
+    }
+
+    Ok(hasher.finish())
+}
+
+#[derive(Default)]
+struct Hasher {
+    size: u64,
+    sha256: Option<sha2::Sha256>,
+    sha1: Option<sha1::Sha1>,
+    md5: Option<md5::Md5>,
+    crc32c: Option<u32>,
+    blake3: Option<blake3::Hasher>,
+}
+
+impl Hasher {
+    fn new(algorithms: &[ChecksumAlgorithm]) -> Self {
+        use sha2::Digest;
+
+        let mut hasher = Hasher::default();
+
+        for algorithm in algorithms {
+            match algorithm {
+                ChecksumAlgorithm::Sha256 => hasher.sha256 = Some(sha2::Sha256::new()),
+                ChecksumAlgorithm::Sha1 => hasher.sha1 = Some(sha1::Sha1::new()),
+                ChecksumAlgorithm::Md5 => hasher.md5 = Some(md5::Md5::new()),
+                ChecksumAlgorithm::Crc32c => hasher.crc32c = Some(0),
+                ChecksumAlgorithm::Blake3 => hasher.blake3 = Some(blake3::Hasher::new()),
+            }
+        }
+
+        hasher
+    }
+
+    fn update(&mut self, chunk: &[u8]) {
+        use sha2::Digest;
+
+        self.size += chunk.len() as u64;
+
+        if let Some(hasher) = &mut self.sha256 {
+            hasher.update(chunk);
+        }
+        if let Some(hasher) = &mut self.sha1 {
+            hasher.update(chunk);
+        }
+        if let Some(hasher) = &mut self.md5 {
+            hasher.update(chunk);
+        }
+        if let Some(crc) = &mut self.crc32c {
+            *crc = crc32c::crc32c_append(*crc, chunk);
+        }
+        if let Some(hasher) = &mut self.blake3 {
+            hasher.update(chunk);
+        }
+    }
+
+    fn finish(self) -> ChecksumResponse {
+        use sha2::Digest;
+
+        ChecksumResponse {
+            size: self.size,
+            sha256: self.sha256.map(|hasher| hex::encode(hasher.finalize())),
+            sha1: self.sha1.map(|hasher| hex::encode(hasher.finalize())),
+            md5: self.md5.map(|hasher| hex::encode(hasher.finalize())),
+            crc32c: self.crc32c.map(|crc| hex::encode(crc.to_be_bytes())),
+            blake3: self
+                .blake3
+                .map(|hasher| hasher.finalize().to_hex().to_string()),
+        }
+    }
+}
+
+/// Encoding of inline content.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Encoding {
+    /// Content is UTF-8 text.
+    #[default]
+    Text,
+    /// Content is base64 encoded binary data.
+    Base64,
+}
+
+impl Encoding {
+    pub(crate) fn encode(self, content: Vec<u8>) -> Result<String, Error> {
+        match self {
+            Encoding::Text => String::from_utf8(content).map_err(|_| {
+                TerminalError::new_with_code(
+                    422,
+                    "content is not valid UTF-8, use base64 encoding instead",
+                )
+                .into()
+            }),
+            Encoding::Base64 => Ok(BASE64_STANDARD.encode(content)),
+        }
+    }
+
+    pub(crate) fn decode(self, content: String) -> Result<Vec<u8>, Error> {
+        match self {
+            Encoding::Text => Ok(content.into_bytes()),
+            Encoding::Base64 => BASE64_STANDARD.decode(content).map_err(|err| {
+                TerminalError::new_with_code(400, format!("invalid base64 content: {err}")).into()
+            }),
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
@@ -174,6 +2135,78 @@ pub(crate) async fn presign_stat<_L: LocationType>(
     Ok(presigned.into())
 }
 
+pub(crate) async fn presign_write<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: PresignRequest<_L, WriteOptions>,
+) -> Result<PresignResponse, Error> {
+    let presigned;
+
+    if let Some(options) = request.options {
+        presigned = operator
+            .presign_write_options(path, request.expiration, options.into())
+            .await?;
+    } else {
+        presigned = operator.presign_write(path, request.expiration).await?;
+    }
+
+    Ok(presigned.into())
+}
+
+pub(crate) async fn presign_delete<_L: LocationType>(
+    operator: &opendal::Operator,
+    path: &str,
+    request: PresignRequest<_L, DeleteOptions>,
+) -> Result<PresignResponse, Error> {
+    let presigned;
+
+    if let Some(options) = request.options {
+        presigned = operator
+            .presign_delete_options(path, request.expiration, options.into())
+            .await?;
+    } else {
+        presigned = operator.presign_delete(path, request.expiration).await?;
+    }
+
+    Ok(presigned.into())
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[serde(bound(deserialize = "Location: LocationType"))]
+#[schemars(bound = "Location: LocationType", example = example_capabilities_request::<Location>())]
+pub struct CapabilitiesRequest<Location: LocationType> {
+    pub location: Location,
+}
+
+pub(crate) fn example_capabilities_request<Location: LocationType>() -> CapabilitiesRequest<Location>
+{
+    CapabilitiesRequest {
+        location: Location::example(),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilitiesResponse {
+    pub info: OperatorInfo,
+    /// Full capability set of the store.
+    pub capability: Capability,
+}
+
+pub(crate) async fn capabilities<_L: LocationType>(
+    operator: &opendal::Operator,
+    _path: &str,
+    _request: CapabilitiesRequest<_L>,
+) -> Result<CapabilitiesResponse, Error> {
+    let info = operator.info();
+
+    Ok(CapabilitiesResponse {
+        capability: info.full_capability().into(),
+        info: info.into(),
+    })
+}
+
 #[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Entry {
@@ -232,28 +2265,464 @@ impl From<opendal::Metadata> for Metadata {
     }
 }
 
-#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+/// Details of an error that occurred while processing a location.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorDetails {
+    /// Kind of the error (eg. `NotFound`, `PermissionDenied`).
+    pub kind: String,
+    pub message: String,
+    /// Whether retrying the operation might succeed.
+    pub temporary: bool,
+}
+
+impl From<opendal::Error> for ErrorDetails {
+    fn from(err: opendal::Error) -> Self {
+        ErrorDetails {
+            kind: err.kind().to_string(),
+            message: err.to_string(),
+            temporary: err.is_temporary(),
+        }
+    }
+}
+
+/// Information about the store an operator is bound to.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OperatorInfo {
+    /// Scheme of the store (eg. `s3`, `fs`).
+    pub scheme: String,
+    /// Root path of the operator.
+    pub root: String,
+    /// Name of the store (eg. bucket name).
+    pub name: String,
+}
+
+impl From<opendal::OperatorInfo> for OperatorInfo {
+    fn from(val: opendal::OperatorInfo) -> Self {
+        OperatorInfo {
+            scheme: val.scheme().to_string(),
+            root: val.root().to_string(),
+            name: val.name().to_string(),
+        }
+    }
+}
+
+/// Capabilities supported by a store.
+#[derive(Default, Debug, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Capability {
+    /// Indicates if the operator supports metadata retrieval operations.
+    pub stat: bool,
+    /// Indicates if conditional stat operations using If-Match are supported.
+    pub stat_with_if_match: bool,
+    /// Indicates if conditional stat operations using If-None-Match are supported.
+    pub stat_with_if_none_match: bool,
+    /// Indicates if conditional stat operations using If-Modified-Since are supported.
+    pub stat_with_if_modified_since: bool,
+    /// Indicates if conditional stat operations using If-Unmodified-Since are supported.
+    pub stat_with_if_unmodified_since: bool,
+    /// Indicates if Cache-Control header override is supported during stat operations.
+    pub stat_with_override_cache_control: bool,
+    /// Indicates if Content-Disposition header override is supported during stat operations.
+    pub stat_with_override_content_disposition: bool,
+    /// Indicates if Content-Type header override is supported during stat operations.
+    pub stat_with_override_content_type: bool,
+    /// Indicates if versions stat operations are supported.
+    pub stat_with_version: bool,
+
+    /// Indicates if the operator supports read operations.
+    pub read: bool,
+    /// Indicates if conditional read operations using If-Match are supported.
+    pub read_with_if_match: bool,
+    /// Indicates if conditional read operations using If-None-Match are supported.
+    pub read_with_if_none_match: bool,
+    /// Indicates if conditional read operations using If-Modified-Since are supported.
+    pub read_with_if_modified_since: bool,
+    /// Indicates if conditional read operations using If-Unmodified-Since are supported.
+    pub read_with_if_unmodified_since: bool,
+    /// Indicates if Cache-Control header override is supported during read operations.
+    pub read_with_override_cache_control: bool,
+    /// Indicates if Content-Disposition header override is supported during read operations.
+    pub read_with_override_content_disposition: bool,
+    /// Indicates if Content-Type header override is supported during read operations.
+    pub read_with_override_content_type: bool,
+    /// Indicates if versions read operations are supported.
+    pub read_with_version: bool,
+
+    /// Indicates if the operator supports write operations.
+    pub write: bool,
+    /// Indicates if multiple write operations can be performed on the same object.
+    pub write_can_multi: bool,
+    /// Indicates if writing empty content is supported.
+    pub write_can_empty: bool,
+    /// Indicates if append operations are supported.
+    pub write_can_append: bool,
+    /// Indicates if Content-Type can be specified during write operations.
+    pub write_with_content_type: bool,
+    /// Indicates if Content-Disposition can be specified during write operations.
+    pub write_with_content_disposition: bool,
+    /// Indicates if Content-Encoding can be specified during write operations.
+    pub write_with_content_encoding: bool,
+    /// Indicates if Cache-Control can be specified during write operations.
+    pub write_with_cache_control: bool,
+    /// Indicates if conditional write operations using If-Match are supported.
+    pub write_with_if_match: bool,
+    /// Indicates if conditional write operations using If-None-Match are supported.
+    pub write_with_if_none_match: bool,
+    /// Indicates if write operations can be conditional on object non-existence.
+    pub write_with_if_not_exists: bool,
+    /// Indicates if custom user metadata can be attached during write operations.
+    pub write_with_user_metadata: bool,
+    /// Maximum size supported for multipart uploads.
+    /// For example, AWS S3 supports up to 5GiB per part in multipart uploads.
+    pub write_multi_max_size: Option<usize>,
+    /// Minimum size required for multipart uploads (except for the last part).
+    /// For example, AWS S3 requires at least 5MiB per part.
+    pub write_multi_min_size: Option<usize>,
+    /// Maximum total size supported for write operations.
+    /// For example, Cloudflare D1 has a 1MB total size limit.
+    pub write_total_max_size: Option<usize>,
+
+    /// Indicates if directory creation is supported.
+    pub create_dir: bool,
+
+    /// Indicates if delete operations are supported.
+    pub delete: bool,
+    /// Indicates if versions delete operations are supported.
+    pub delete_with_version: bool,
+    /// Maximum size supported for single delete operations.
+    pub delete_max_size: Option<usize>,
+
+    /// Indicates if copy operations are supported.
+    pub copy: bool,
+    /// Indicates if conditional copy operations with if-not-exists are supported.
+    pub copy_with_if_not_exists: bool,
+
+    /// Indicates if rename operations are supported.
+    pub rename: bool,
+
+    /// Indicates if list operations are supported.
+    pub list: bool,
+    /// Indicates if list operations support result limiting.
+    pub list_with_limit: bool,
+    /// Indicates if list operations support continuation from a specific point.
+    pub list_with_start_after: bool,
+    /// Indicates if recursive listing is supported.
+    pub list_with_recursive: bool,
+    /// Indicates if listing with versions included is supported.
+    pub list_with_versions: bool,
+    /// Indicates if listing with deleted files included is supported.
+    pub list_with_deleted: bool,
+
+    /// Indicates if presigned URL generation is supported.
+    pub presign: bool,
+    /// Indicates if presigned URLs for read operations are supported.
+    pub presign_read: bool,
+    /// Indicates if presigned URLs for stat operations are supported.
+    pub presign_stat: bool,
+    /// Indicates if presigned URLs for write operations are supported.
+    pub presign_write: bool,
+    /// Indicates if presigned URLs for delete operations are supported.
+    pub presign_delete: bool,
+
+    /// Indicate if the operator supports shared access.
+    pub shared: bool,
+}
+
+impl From<opendal::Capability> for Capability {
+    fn from(val: opendal::Capability) -> Self {
+        Capability {
+            stat: val.stat,
+            stat_with_if_match: val.stat_with_if_match,
+            stat_with_if_none_match: val.stat_with_if_none_match,
+            stat_with_if_modified_since: val.stat_with_if_modified_since,
+            stat_with_if_unmodified_since: val.stat_with_if_unmodified_since,
+            stat_with_override_cache_control: val.stat_with_override_cache_control,
+            stat_with_override_content_disposition: val.stat_with_override_content_disposition,
+            stat_with_override_content_type: val.stat_with_override_content_type,
+            stat_with_version: val.stat_with_version,
+            read: val.read,
+            read_with_if_match: val.read_with_if_match,
+            read_with_if_none_match: val.read_with_if_none_match,
+            read_with_if_modified_since: val.read_with_if_modified_since,
+            read_with_if_unmodified_since: val.read_with_if_unmodified_since,
+            read_with_override_cache_control: val.read_with_override_cache_control,
+            read_with_override_content_disposition: val.read_with_override_content_disposition,
+            read_with_override_content_type: val.read_with_override_content_type,
+            read_with_version: val.read_with_version,
+            write: val.write,
+            write_can_multi: val.write_can_multi,
+            write_can_empty: val.write_can_empty,
+            write_can_append: val.write_can_append,
+            write_with_content_type: val.write_with_content_type,
+            write_with_content_disposition: val.write_with_content_disposition,
+            write_with_content_encoding: val.write_with_content_encoding,
+            write_with_cache_control: val.write_with_cache_control,
+            write_with_if_match: val.write_with_if_match,
+            write_with_if_none_match: val.write_with_if_none_match,
+            write_with_if_not_exists: val.write_with_if_not_exists,
+            write_with_user_metadata: val.write_with_user_metadata,
+            write_multi_max_size: val.write_multi_max_size,
+            write_multi_min_size: val.write_multi_min_size,
+            write_total_max_size: val.write_total_max_size,
+            create_dir: val.create_dir,
+            delete: val.delete,
+            delete_with_version: val.delete_with_version,
+            delete_max_size: val.delete_max_size,
+            copy: val.copy,
+            copy_with_if_not_exists: val.copy_with_if_not_exists,
+            rename: val.rename,
+            list: val.list,
+            list_with_limit: val.list_with_limit,
+            list_with_start_after: val.list_with_start_after,
+            list_with_recursive: val.list_with_recursive,
+            list_with_versions: val.list_with_versions,
+            list_with_deleted: val.list_with_deleted,
+            presign: val.presign,
+            presign_read: val.presign_read,
+            presign_stat: val.presign_stat,
+            presign_write: val.presign_write,
+            presign_delete: val.presign_delete,
+            shared: val.shared,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum EntryMode {
+    /// FILE means the path has data to read.
+    File,
+    /// DIR means the path can be listed.
+    Dir,
+    /// Unknown means we don't know what we can do on this path.
+    #[default]
+    Unknown,
+}
+
+impl From<opendal::EntryMode> for EntryMode {
+    fn from(val: opendal::EntryMode) -> Self {
+        match val {
+            opendal::EntryMode::FILE => EntryMode::File,
+            opendal::EntryMode::DIR => EntryMode::Dir,
+            opendal::EntryMode::Unknown => EntryMode::Unknown,
+        }
+    }
+}
+
+/// Filter for list operations.
+///
+/// Patterns are matched against entry paths relative to the listed directory.
+/// In glob patterns `*` does not match `/`, use `**` to match any number of directories
+/// (eg. `**/*.parquet`).
+///
+/// Metadata filters are evaluated against the metadata returned by the listing,
+/// which may be incomplete depending on the store.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ListFilter {
+    /// Only return entries matching any of these glob patterns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Do not return entries matching any of these glob patterns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Only return entries matching any of these regular expressions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_regex: Vec<String>,
+    /// Do not return entries matching any of these regular expressions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_regex: Vec<String>,
+
+    /// Only return entries with at least this many bytes.
+    pub min_size: Option<u64>,
+    /// Only return entries with at most this many bytes.
+    pub max_size: Option<u64>,
+    /// Only return entries modified after this time.
+    ///
+    /// Entries without a modification time are not returned.
+    pub modified_after: Option<jiff::Timestamp>,
+    /// Only return entries modified before this time.
+    ///
+    /// Entries without a modification time are not returned.
+    pub modified_before: Option<jiff::Timestamp>,
+    /// Only return entries with any of these content types.
+    ///
+    /// Types ending with `/*` match any subtype (eg. `image/*`).
+    /// Entries without a content type are not returned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_types: Vec<String>,
+    /// Only return entries with any of these modes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<EntryMode>,
+}
+
+impl ListFilter {
+    fn matcher(self, path: &str) -> Result<ListMatcher, Error> {
+        // Listing "dir/prefix" returns entries of "dir/" starting with "prefix".
+        let path = path.trim_start_matches('/');
+        let base = match path.rfind('/') {
+            Some(i) => &path[..=i],
+            None => "",
+        };
+
+        Ok(ListMatcher {
+            base: base.to_string(),
+            has_include: !self.include.is_empty() || !self.include_regex.is_empty(),
+            include: glob_set(&self.include)?,
+            exclude: glob_set(&self.exclude)?,
+            include_regex: regex_set(&self.include_regex)?,
+            exclude_regex: regex_set(&self.exclude_regex)?,
+            min_size: self.min_size,
+            max_size: self.max_size,
+            modified_after: self.modified_after,
+            modified_before: self.modified_before,
+            content_types: self.content_types,
+            modes: self.modes,
+        })
+    }
+}
+
+struct ListMatcher {
+    base: String,
+    has_include: bool,
+    include: globset::GlobSet,
+    exclude: globset::GlobSet,
+    include_regex: regex::RegexSet,
+    exclude_regex: regex::RegexSet,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    modified_after: Option<jiff::Timestamp>,
+    modified_before: Option<jiff::Timestamp>,
+    content_types: Vec<String>,
+    modes: Vec<EntryMode>,
+}
+
+impl ListMatcher {
+    fn is_path_match(&self, path: &str) -> bool {
+        let path = path.strip_prefix(self.base.as_str()).unwrap_or(path);
+
+        if self.has_include && !self.include.is_match(path) && !self.include_regex.is_match(path) {
+            return false;
+        }
+
+        !self.exclude.is_match(path) && !self.exclude_regex.is_match(path)
+    }
+
+    fn is_metadata_match(&self, metadata: &opendal::Metadata) -> bool {
+        let size = metadata.content_length();
+
+        if self.min_size.is_some_and(|min| size < min)
+            || self.max_size.is_some_and(|max| size > max)
+        {
+            return false;
+        }
+
+        if self.modified_after.is_some() || self.modified_before.is_some() {
+            let Some(modified) = metadata.last_modified().map(|t| t.into_inner()) else {
+                return false;
+            };
+
+            if self.modified_after.is_some_and(|after| modified <= after)
+                || self
+                    .modified_before
+                    .is_some_and(|before| modified >= before)
+            {
+                return false;
+            }
+        }
+
+        if !self.content_types.is_empty() {
+            let Some(content_type) = metadata.content_type() else {
+                return false;
+            };
+
+            if !self
+                .content_types
+                .iter()
+                .any(|pattern| content_type_matches(pattern, content_type))
+            {
+                return false;
+            }
+        }
+
+        self.modes.is_empty() || self.modes.contains(&metadata.mode().into())
+    }
+}
+
+fn content_type_matches(pattern: &str, content_type: &str) -> bool {
+    // Ignore parameters, eg. "text/plain; charset=utf-8"
+    let content_type = content_type.split(';').next().unwrap_or_default().trim();
+
+    match pattern.strip_suffix("/*") {
+        Some(prefix) => content_type
+            .split_once('/')
+            .is_some_and(|(t, _)| t.eq_ignore_ascii_case(prefix)),
+        None => content_type.eq_ignore_ascii_case(pattern),
+    }
+}
+
+/// Sort order for list operations.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
-pub enum EntryMode {
-    /// FILE means the path has data to read.
-    File,
-    /// DIR means the path can be listed.
-    Dir,
-    /// Unknown means we don't know what we can do on this path.
-    #[default]
-    Unknown,
+pub struct ListSort {
+    /// Field to sort by.
+    pub by: SortBy,
+    /// Sort in descending order.
+    #[serde(default)]
+    pub descending: bool,
 }
 
-impl From<opendal::EntryMode> for EntryMode {
-    fn from(val: opendal::EntryMode) -> Self {
-        match val {
-            opendal::EntryMode::FILE => EntryMode::File,
-            opendal::EntryMode::DIR => EntryMode::Dir,
-            opendal::EntryMode::Unknown => EntryMode::Unknown,
+impl ListSort {
+    fn apply(&self, entries: &mut [Entry]) {
+        match self.by {
+            SortBy::Name => entries.sort_by(|a, b| a.path.cmp(&b.path)),
+            SortBy::Size => entries.sort_by_key(|entry| entry.metadata.content_length),
+            SortBy::LastModified => entries.sort_by_key(|entry| entry.metadata.last_modified),
+        }
+
+        if self.descending {
+            entries.reverse();
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SortBy {
+    /// Sort by path.
+    Name,
+    /// Sort by content length.
+    Size,
+    /// Sort by last modification time.
+    ///
+    /// Entries without a modification time are treated as the oldest.
+    LastModified,
+}
+
+fn glob_set(patterns: &[String]) -> Result<globset::GlobSet, Error> {
+    let mut builder = globset::GlobSetBuilder::new();
+
+    for pattern in patterns {
+        let glob = globset::GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|err| TerminalError::new_with_code(400, err.to_string()))?;
+
+        builder.add(glob);
+    }
+
+    Ok(builder
+        .build()
+        .map_err(|err| TerminalError::new_with_code(400, err.to_string()))?)
+}
+
+fn regex_set(patterns: &[String]) -> Result<regex::RegexSet, Error> {
+    Ok(regex::RegexSet::new(patterns)
+        .map_err(|err| TerminalError::new_with_code(400, err.to_string()))?)
+}
+
 #[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase", remote = "opendal::options::ListOptions")]
 pub struct ListOptionsDef {
@@ -524,3 +2993,718 @@ impl From<StatOptions> for opendal::options::StatOptions {
         }
     }
 }
+
+/// Options for write operations.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteOptions {
+    /// Sets `Cache-Control` header for this write operation.
+    ///
+    /// If not supported by the underlying service, the value will be ignored.
+    pub cache_control: Option<String>,
+    /// Sets `Content-Type` header for this write operation.
+    ///
+    /// If not supported by the underlying service, the value will be ignored.
+    pub content_type: Option<String>,
+    /// Sets `Content-Disposition` header for this write operation.
+    ///
+    /// If not supported by the underlying service, the value will be ignored.
+    pub content_disposition: Option<String>,
+    /// Sets `Content-Encoding` header for this write operation.
+    ///
+    /// If not supported by the underlying service, the value will be ignored.
+    pub content_encoding: Option<String>,
+    /// Sets user metadata for this write operation.
+    ///
+    /// If not supported by the underlying service, the metadata will be ignored.
+    pub user_metadata: Option<HashMap<String, String>>,
+
+    /// Sets `If-Match` header for this write operation.
+    ///
+    /// The write operation will only succeed if the target's `ETag` matches the given `ETag`.
+    pub if_match: Option<String>,
+    /// Sets `If-None-Match` header for this write operation.
+    ///
+    /// The write operation will only succeed if the target's `ETag` doesn't match the given `ETag`.
+    pub if_none_match: Option<String>,
+    /// Sets the condition that the write operation will succeed only if the target does not exist.
+    #[serde(default)]
+    pub if_not_exists: bool,
+
+    /// Set `concurrent` for the operation.
+    ///
+    /// By setting `concurrent`, opendal will write chunks concurrently on supported storage services.
+    #[serde(default)]
+    pub concurrent: usize,
+    /// Set `chunk` for the operation.
+    ///
+    /// OpenDAL will use services' preferred chunk size by default. Users can set chunk based on their own needs.
+    pub chunk: Option<usize>,
+}
+
+impl From<WriteOptions> for opendal::options::WriteOptions {
+    fn from(options: WriteOptions) -> Self {
+        Self {
+            append: false,
+            cache_control: options.cache_control,
+            content_type: options.content_type,
+            content_disposition: options.content_disposition,
+            content_encoding: options.content_encoding,
+            user_metadata: options.user_metadata,
+            if_match: options.if_match,
+            if_none_match: options.if_none_match,
+            if_not_exists: options.if_not_exists,
+            concurrent: options.concurrent,
+            chunk: options.chunk,
+        }
+    }
+}
+
+/// Options for append operations.
+///
+/// Options are only applied when the object is created by the append.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppendOptions {
+    /// Sets `Cache-Control` header for this append operation.
+    pub cache_control: Option<String>,
+    /// Sets `Content-Type` header for this append operation.
+    pub content_type: Option<String>,
+    /// Sets `Content-Disposition` header for this append operation.
+    pub content_disposition: Option<String>,
+    /// Sets `Content-Encoding` header for this append operation.
+    pub content_encoding: Option<String>,
+    /// Sets user metadata for this append operation.
+    pub user_metadata: Option<HashMap<String, String>>,
+}
+
+impl From<AppendOptions> for opendal::options::WriteOptions {
+    fn from(options: AppendOptions) -> Self {
+        Self {
+            append: true,
+            cache_control: options.cache_control,
+            content_type: options.content_type,
+            content_disposition: options.content_disposition,
+            content_encoding: options.content_encoding,
+            user_metadata: options.user_metadata,
+            ..Default::default()
+        }
+    }
+}
+
+/// Options for delete operations.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteOptions {
+    /// Set `version` for this operation.
+    ///
+    /// This option can be used to delete a specified version of the given path.
+    pub version: Option<String>,
+}
+
+impl From<DeleteOptions> for opendal::options::DeleteOptions {
+    fn from(options: DeleteOptions) -> Self {
+        Self {
+            version: options.version,
+        }
+    }
+}
+
+/// Options for copy operations.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyOptions {
+    /// Sets the condition that copy operation will succeed only if target does not exist.
+    #[serde(default)]
+    pub if_not_exists: bool,
+}
+
+impl From<CopyOptions> for opendal::options::CopyOptions {
+    fn from(options: CopyOptions) -> Self {
+        Self {
+            if_not_exists: options.if_not_exists,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory() -> opendal::Operator {
+        opendal::Operator::new(opendal::services::Memory::default())
+            .unwrap()
+            .finish()
+    }
+
+    fn list_request(page_size: Option<usize>, cursor: Option<String>) -> ListRequest<String> {
+        ListRequest {
+            location: "dir/".to_string(),
+            page_size,
+            cursor,
+            ..example_list_request()
+        }
+    }
+
+    #[test]
+    fn cursor_roundtrip() {
+        let cursor = Cursor {
+            position: 42,
+            path: "dir/a:b.txt".to_string(),
+        };
+
+        assert_eq!(decode_cursor(&encode_cursor(&cursor)).unwrap(), cursor);
+    }
+
+    #[test]
+    fn invalid_cursor() {
+        assert!(decode_cursor("not base64!").is_err());
+        assert!(decode_cursor(&BASE64_URL_SAFE_NO_PAD.encode("dir/a.txt")).is_err());
+        assert!(decode_cursor(&BASE64_URL_SAFE_NO_PAD.encode("x:dir/a.txt")).is_err());
+    }
+
+    #[tokio::test]
+    async fn list_pages_return_every_entry_once() {
+        let operator = memory();
+
+        for name in ["zz", "mm", "aa", "bb"] {
+            operator
+                .write(&format!("dir/{name}.txt"), "hello")
+                .await
+                .unwrap();
+        }
+
+        let all = list(&operator, "dir/", list_request(None, None))
+            .await
+            .unwrap()
+            .entries;
+
+        let mut paged = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let response = list(&operator, "dir/", list_request(Some(1), cursor))
+                .await
+                .unwrap();
+
+            paged.extend(response.entries);
+            cursor = response.next_cursor;
+
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let paths = |entries: &[Entry]| {
+            let mut paths: Vec<_> = entries.iter().map(|entry| entry.path.clone()).collect();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(paths(&paged), paths(&all));
+        assert_eq!(paged.len(), all.len());
+    }
+
+    #[test]
+    fn entry_names() {
+        assert_eq!(entry_name("dir/a.txt"), "a.txt");
+        assert_eq!(entry_name("dir/sub/"), "sub/");
+        assert_eq!(entry_name("a.txt"), "a.txt");
+        assert_eq!(entry_name("/"), "");
+        assert_eq!(entry_name(""), "");
+    }
+
+    #[test]
+    fn content_range_known_length() {
+        let range = ContentRange::new(10, 2, Some(20));
+
+        assert_eq!(range.size(), Some(8));
+        assert_eq!(range.bounds(), (Bound::Included(2), Bound::Excluded(10)));
+        assert!(!range.is_empty());
+
+        assert!(ContentRange::new(10, 12, None).is_empty());
+    }
+
+    #[test]
+    fn content_range_unknown_length() {
+        let range = ContentRange::new(0, 2, Some(3));
+
+        assert_eq!(range.size(), None);
+        assert_eq!(range.bounds(), (Bound::Included(2), Bound::Unbounded));
+        assert!(!range.is_empty());
+        assert_eq!(range.within(b"hello", 0), b"hel");
+        assert_eq!(range.within(b"hello", 2), b"h");
+        assert!(range.is_complete(3));
+
+        let range = ContentRange::new(0, 0, None);
+
+        assert_eq!(range.within(b"hello", 100), b"hello");
+        assert!(!range.is_complete(100));
+    }
+
+    #[tokio::test]
+    async fn read_range() {
+        let operator = memory();
+
+        operator.write("a.txt", "hello world").await.unwrap();
+
+        let request = |offset, size, max_size| ReadRequest {
+            location: "a.txt".to_string(),
+            encoding: Encoding::Text,
+            max_size,
+            options: Some(ReadOptions {
+                range: BytesRange { offset, size },
+                ..Default::default()
+            }),
+        };
+
+        let response = read(&operator, "a.txt", request(6, Some(100), None))
+            .await
+            .unwrap();
+
+        assert_eq!(response.content, "world");
+
+        assert!(
+            read(&operator, "a.txt", request(0, None, Some(5)))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn checksum_range() {
+        let operator = memory();
+
+        operator.write("a.txt", "hello world").await.unwrap();
+
+        let response = checksum(
+            &operator,
+            "a.txt",
+            ChecksumRequest {
+                location: "a.txt".to_string(),
+                algorithms: vec![],
+                range: Some(BytesRange {
+                    offset: 6,
+                    size: Some(100),
+                }),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.size, 5);
+        assert_eq!(
+            response.sha256.as_deref(),
+            Some("486ea46224d1bb4fb680f34f7c9ad96a8f24ec88be73ea8e5a6c65260e9cb8a7")
+        );
+    }
+
+    #[tokio::test]
+    async fn remove_all_directory() {
+        let operator = memory();
+
+        for path in ["dir/a.txt", "dir/sub/b.txt", "directory/c.txt", "dirty.txt"] {
+            operator.write(path, "hello").await.unwrap();
+        }
+
+        let request = |location: &str| RemoveAllRequest {
+            location: location.to_string(),
+            batch_size: None,
+        };
+
+        let response = remove_all(&operator, "dir", request("dir")).await.unwrap();
+
+        assert_eq!(response.deleted, 2);
+        assert!(!operator.exists("dir/a.txt").await.unwrap());
+        assert!(operator.exists("directory/c.txt").await.unwrap());
+        assert!(operator.exists("dirty.txt").await.unwrap());
+
+        assert!(remove_all(&operator, "/", request("/")).await.is_err());
+        assert!(remove_all(&operator, "", request("")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn copy_if_not_exists() {
+        let operator = memory();
+
+        operator.write("a.txt", "hello").await.unwrap();
+        operator.write("c.txt", "world").await.unwrap();
+
+        let request = |destination: &str| CopyRequest {
+            source: "a.txt".to_string(),
+            destination: destination.to_string(),
+            options: Some(CopyOptions {
+                if_not_exists: true,
+            }),
+        };
+
+        copy(
+            &operator,
+            "a.txt",
+            &operator,
+            "b.txt",
+            true,
+            request("b.txt"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(operator.read("b.txt").await.unwrap().to_vec(), b"hello");
+
+        let result = copy(
+            &operator,
+            "a.txt",
+            &operator,
+            "c.txt",
+            true,
+            request("c.txt"),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(operator.read("c.txt").await.unwrap().to_vec(), b"world");
+    }
+
+    #[tokio::test]
+    async fn export_inventory_skips_itself() {
+        let operator = memory();
+
+        operator.write("dir/a.txt", "hello").await.unwrap();
+        operator.write("dir/sub/b.txt", "hi").await.unwrap();
+
+        let response = export_inventory(
+            &operator,
+            "dir/",
+            &operator,
+            "dir/inventory.csv",
+            true,
+            ExportInventoryRequest {
+                source: "dir/".to_string(),
+                destination: "dir/inventory.csv".to_string(),
+                format: InventoryFormat::Csv,
+                stat: true,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.count, 2);
+
+        // A second export lists the first inventory, but never the one being written.
+        let response = export_inventory(
+            &operator,
+            "dir/",
+            &operator,
+            "dir/inventory.csv",
+            true,
+            ExportInventoryRequest {
+                source: "dir/".to_string(),
+                destination: "dir/inventory.csv".to_string(),
+                format: InventoryFormat::Csv,
+                stat: true,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.count, 2);
+    }
+
+    #[test]
+    fn csv_fields() {
+        assert_eq!(csv_field("dir/a.txt"), "dir/a.txt");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("a \"b\""), "\"a \"\"b\"\"\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+    #[test]
+    fn list_matcher_paths() {
+        let matcher = ListFilter {
+            include: vec!["*.txt".to_string()],
+            exclude_regex: vec!["^secret".to_string()],
+            ..Default::default()
+        }
+        .matcher("/dir/")
+        .unwrap();
+
+        assert!(matcher.is_path_match("dir/a.txt"));
+        assert!(!matcher.is_path_match("dir/a.csv"));
+        assert!(!matcher.is_path_match("dir/sub/a.txt"));
+        assert!(!matcher.is_path_match("dir/secret.txt"));
+
+        let matcher = ListFilter {
+            include: vec!["**/*.parquet".to_string()],
+            include_regex: vec![r"\.csv$".to_string()],
+            exclude: vec!["tmp/**".to_string()],
+            ..Default::default()
+        }
+        .matcher("dir/2024")
+        .unwrap();
+
+        assert!(matcher.is_path_match("dir/2024/01/a.parquet"));
+        assert!(matcher.is_path_match("dir/2024/a.csv"));
+        assert!(!matcher.is_path_match("dir/2024/a.json"));
+        assert!(!matcher.is_path_match("dir/tmp/a.parquet"));
+    }
+
+    #[test]
+    fn list_matcher_invalid_patterns() {
+        let invalid_glob = ListFilter {
+            include: vec!["a[".to_string()],
+            ..Default::default()
+        };
+        let invalid_regex = ListFilter {
+            exclude_regex: vec!["(".to_string()],
+            ..Default::default()
+        };
+
+        assert!(invalid_glob.matcher("dir/").is_err());
+        assert!(invalid_regex.matcher("dir/").is_err());
+    }
+
+    #[tokio::test]
+    async fn list_filters_paths() {
+        let operator = memory();
+
+        for path in ["dir/a.parquet", "dir/b.csv", "dir/sub/c.parquet"] {
+            operator.write(path, "hello").await.unwrap();
+        }
+
+        let request = ListRequest {
+            options: Some(opendal::options::ListOptions {
+                recursive: true,
+                ..Default::default()
+            }),
+            filter: Some(ListFilter {
+                include: vec!["**/*.parquet".to_string()],
+                ..Default::default()
+            }),
+            ..list_request(None, None)
+        };
+
+        let mut paths: Vec<_> = list(&operator, "dir/", request)
+            .await
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+        paths.sort();
+
+        assert_eq!(paths, ["dir/a.parquet", "dir/sub/c.parquet"]);
+    }
+    #[test]
+    fn list_matcher_metadata() {
+        let matcher = ListFilter {
+            min_size: Some(2),
+            max_size: Some(10),
+            content_types: vec!["text/*".to_string()],
+            ..Default::default()
+        }
+        .matcher("dir/")
+        .unwrap();
+
+        let metadata = |size: u64, content_type: &str| {
+            let mut metadata = opendal::Metadata::new(opendal::EntryMode::FILE);
+            metadata.set_content_length(size);
+            metadata.set_content_type(content_type);
+            metadata
+        };
+
+        assert!(matcher.is_metadata_match(&metadata(5, "text/plain")));
+        assert!(!matcher.is_metadata_match(&metadata(1, "text/plain")));
+        assert!(!matcher.is_metadata_match(&metadata(11, "text/plain")));
+        assert!(!matcher.is_metadata_match(&metadata(5, "image/png")));
+    }
+
+    #[test]
+    fn content_type_patterns() {
+        assert!(content_type_matches("text/plain", "text/plain"));
+        assert!(content_type_matches(
+            "text/plain",
+            "Text/Plain; charset=utf-8"
+        ));
+        assert!(content_type_matches("text/*", "text/csv"));
+        assert!(!content_type_matches("text/*", "application/json"));
+        assert!(!content_type_matches("text/plain", "text/csv"));
+    }
+
+    #[tokio::test]
+    async fn list_sorts_and_caps_entries() {
+        let operator = memory();
+
+        for (name, content) in [("a", "xxx"), ("b", "x"), ("c", "xx")] {
+            operator
+                .write(&format!("dir/{name}.txt"), content)
+                .await
+                .unwrap();
+        }
+
+        let request = ListRequest {
+            sort: Some(ListSort {
+                by: SortBy::Size,
+                descending: true,
+            }),
+            max_entries: Some(2),
+            stat: true,
+            ..list_request(None, None)
+        };
+
+        let paths: Vec<_> = list(&operator, "dir/", request)
+            .await
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+
+        assert_eq!(paths, ["dir/a.txt", "dir/c.txt"]);
+
+        let request = ListRequest {
+            max_entries: Some(2),
+            ..list_request(Some(1), None)
+        };
+
+        assert!(list(&operator, "dir/", request).await.is_err());
+    }
+    #[tokio::test]
+    async fn tree_depth_limit() {
+        let operator = memory();
+
+        for path in ["dir/a.txt", "dir/sub/b.txt", "dir/sub/deep/c.txt"] {
+            operator.write(path, "hello").await.unwrap();
+        }
+
+        let request = TreeRequest {
+            location: "dir/".to_string(),
+            depth: Some(2),
+        };
+
+        let root = tree(&operator, "dir/", request).await.unwrap();
+
+        assert_eq!(root.file_count, Some(2));
+
+        let children = root.children.unwrap();
+        let sub = children
+            .iter()
+            .find(|node| node.path == "dir/sub/")
+            .unwrap();
+
+        assert_eq!(sub.file_count, Some(1));
+
+        let sub_children = sub.children.as_ref().unwrap();
+        let deep = sub_children
+            .iter()
+            .find(|node| node.path == "dir/sub/deep/")
+            .unwrap();
+
+        assert_eq!(deep.size, None);
+        assert_eq!(deep.file_count, None);
+        assert!(deep.children.is_none());
+    }
+    /// Fails deleting paths containing "locked".
+    struct LockedLayer;
+
+    impl<A: opendal::raw::Access> opendal::raw::Layer<A> for LockedLayer {
+        type LayeredAccess = LockedAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccess {
+            LockedAccessor(inner)
+        }
+    }
+
+    #[derive(Debug)]
+    struct LockedAccessor<A>(A);
+
+    impl<A: opendal::raw::Access> opendal::raw::LayeredAccess for LockedAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type Writer = A::Writer;
+        type Lister = A::Lister;
+        type Deleter = LockedDeleter<A::Deleter>;
+
+        fn inner(&self) -> &A {
+            &self.0
+        }
+
+        async fn read(
+            &self,
+            path: &str,
+            args: opendal::raw::OpRead,
+        ) -> opendal::Result<(opendal::raw::RpRead, A::Reader)> {
+            self.0.read(path, args).await
+        }
+
+        async fn write(
+            &self,
+            path: &str,
+            args: opendal::raw::OpWrite,
+        ) -> opendal::Result<(opendal::raw::RpWrite, A::Writer)> {
+            self.0.write(path, args).await
+        }
+
+        async fn delete(&self) -> opendal::Result<(opendal::raw::RpDelete, Self::Deleter)> {
+            let (rp, deleter) = self.0.delete().await?;
+
+            Ok((rp, LockedDeleter(deleter)))
+        }
+
+        async fn list(
+            &self,
+            path: &str,
+            args: opendal::raw::OpList,
+        ) -> opendal::Result<(opendal::raw::RpList, A::Lister)> {
+            self.0.list(path, args).await
+        }
+    }
+
+    struct LockedDeleter<D>(D);
+
+    impl<D: opendal::raw::oio::Delete> opendal::raw::oio::Delete for LockedDeleter<D> {
+        fn delete(&mut self, path: &str, args: opendal::raw::OpDelete) -> opendal::Result<()> {
+            if path.contains("locked") {
+                return Err(opendal::Error::new(
+                    opendal::ErrorKind::PermissionDenied,
+                    "locked",
+                ));
+            }
+
+            self.0.delete(path, args)
+        }
+
+        async fn flush(&mut self) -> opendal::Result<usize> {
+            self.0.flush().await
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_batch_results() {
+        let operator = memory().layer(LockedLayer);
+
+        operator.write("dir/a.txt", "hello").await.unwrap();
+        operator.write("dir/locked.txt", "hello").await.unwrap();
+
+        let paths = ["dir/a.txt", "dir/locked.txt", "dir/missing.txt"]
+            .map(String::from)
+            .to_vec();
+
+        let results = delete_batch(
+            &operator,
+            paths,
+            Some(DeleteBatchOptions {
+                check_exists: true,
+                concurrency: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(results[0].status, DeleteStatus::Deleted));
+        assert!(results[0].error.is_none());
+        assert!(matches!(results[1].status, DeleteStatus::Error));
+        assert!(results[1].error.is_some());
+        assert!(matches!(results[2].status, DeleteStatus::NotFound));
+        assert!(!operator.exists("dir/a.txt").await.unwrap());
+        assert!(operator.exists("dir/locked.txt").await.unwrap());
+    }
+}
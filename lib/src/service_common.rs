@@ -4,6 +4,70 @@ pub trait Service {
     /// List entries in a given location.
     async fn list(request: Json<ListRequest>) -> HandlerResult<Json<ListResponse>>;
 
+    /// List a single level of a given location, returning files and prefixes separately.
+    #[name = "listDelimited"]
+    async fn list_delimited(
+        request: Json<ListDelimitedRequest>,
+    ) -> HandlerResult<Json<ListDelimitedResponse>>;
+
+    /// List a given location as a tree down to a given depth.
+    async fn tree(request: Json<TreeRequest>) -> HandlerResult<Json<TreeNode>>;
+
+    /// Search entries under a given location by user metadata.
+    async fn search(request: Json<SearchRequest>) -> HandlerResult<Json<ListResponse>>;
+
+    /// Summarize disk usage under a given location.
+    async fn usage(request: Json<UsageRequest>) -> HandlerResult<Json<UsageResponse>>;
+
+    /// Retrieve metadata of a given location.
+    async fn stat(request: Json<StatRequest>) -> HandlerResult<Json<Metadata>>;
+
+    /// Retrieve metadata of a list of locations.
+    #[name = "statBatch"]
+    async fn stat_batch(request: Json<StatBatchRequest>) -> HandlerResult<Json<StatBatchResponse>>;
+
+    /// Read the content of a given location inline.
+    async fn read(request: Json<ReadRequest>) -> HandlerResult<Json<ReadResponse>>;
+
+    /// Compute digests of the content of a given location.
+    async fn checksum(request: Json<ChecksumRequest>) -> HandlerResult<Json<ChecksumResponse>>;
+
+    /// Write inline content to a given location.
+    async fn write(request: Json<WriteRequest>) -> HandlerResult<Json<Metadata>>;
+
+    /// Append inline content to a given location.
+    async fn append(request: Json<AppendRequest>) -> HandlerResult<Json<AppendResponse>>;
+
+    /// Delete a given location.
+    async fn delete(request: Json<DeleteRequest>) -> HandlerResult<Json<DeleteResponse>>;
+
+    /// Remove all entries under a given location recursively.
+    #[name = "removeAll"]
+    async fn remove_all(request: Json<RemoveAllRequest>) -> HandlerResult<Json<DeleteResponse>>;
+
+    /// Delete a list of locations.
+    #[name = "deleteBatch"]
+    async fn delete_batch(
+        request: Json<DeleteBatchRequest>,
+    ) -> HandlerResult<Json<DeleteBatchResponse>>;
+
+    /// Copy a file within the store.
+    async fn copy(request: Json<CopyRequest>) -> HandlerResult<()>;
+
+    /// Rename a file within the store.
+    async fn rename(request: Json<RenameRequest>) -> HandlerResult<()>;
+
+    /// Retrieve information and capabilities of the store behind a given location.
+    async fn capabilities(
+        request: Json<CapabilitiesRequest>,
+    ) -> HandlerResult<Json<CapabilitiesResponse>>;
+
+    /// Export a recursive listing of a location into another location.
+    #[name = "exportInventory"]
+    async fn export_inventory(
+        request: Json<ExportInventoryRequest>,
+    ) -> HandlerResult<Json<ExportInventoryResponse>>;
+
     /// Presign an operation for read.
     #[name = "presignRead"]
     async fn presign_read(
@@ -15,12 +79,64 @@ pub trait Service {
     async fn presign_stat(
         request: Json<PresignStatRequest>,
     ) -> HandlerResult<Json<PresignResponse>>;
+
+    /// Presign an operation for write.
+    #[name = "presignWrite"]
+    async fn presign_write(
+        request: Json<PresignWriteRequest>,
+    ) -> HandlerResult<Json<PresignResponse>>;
+
+    /// Presign an operation for delete.
+    #[name = "presignDelete"]
+    async fn presign_delete(
+        request: Json<PresignDeleteRequest>,
+    ) -> HandlerResult<Json<PresignResponse>>;
 }
 
 pub type ListRequest = service::ListRequest<Location>;
+pub type ListDelimitedRequest = service::ListRequest<Location>;
+pub type TreeRequest = service::TreeRequest<Location>;
+pub type SearchRequest = service::SearchRequest<Location>;
+pub type UsageRequest = service::UsageRequest<Location>;
+pub type StatRequest = service::StatRequest<Location>;
+pub type StatBatchRequest = service::BatchRequest<Location, StatBatchOptions>;
+pub type StatBatchResponse = service::BatchResponse<Location, StatResult>;
+pub type ReadRequest = service::ReadRequest<Location>;
+pub type ChecksumRequest = service::ChecksumRequest<Location>;
+pub type WriteRequest = service::WriteRequest<Location>;
+pub type AppendRequest = service::AppendRequest<Location>;
+pub type DeleteRequest = service::DeleteRequest<Location>;
+pub type RemoveAllRequest = service::RemoveAllRequest<Location>;
+pub type DeleteBatchRequest = service::BatchRequest<Location, DeleteBatchOptions>;
+pub type DeleteBatchResponse = service::BatchResponse<Location, DeleteResult>;
+pub type CopyRequest = service::CopyRequest<Location>;
+pub type RenameRequest = service::RenameRequest<Location>;
+pub type ExportInventoryRequest = service::ExportInventoryRequest<Location>;
+pub type CapabilitiesRequest = service::CapabilitiesRequest<Location>;
 pub type PresignReadRequest = service::PresignRequest<Location, ReadOptions>;
 pub type PresignStatRequest = service::PresignRequest<Location, StatOptions>;
+pub type PresignWriteRequest = service::PresignRequest<Location, WriteOptions>;
+pub type PresignDeleteRequest = service::PresignRequest<Location, DeleteOptions>;
 
 handler_impl!(list);
+handler_impl!(list_delimited);
+handler_impl!(tree, TreeNode);
+handler_impl!(search, ListResponse);
+handler_impl!(usage);
+handler_impl!(stat, Metadata);
+handler_impl!(batch stat_batch);
+handler_impl!(read);
+handler_impl!(checksum);
+handler_impl!(write, Metadata);
+handler_impl!(append, ());
+handler_impl!(delete);
+handler_impl!(remove_all, DeleteResponse);
+handler_impl!(batch delete_batch);
+handler_impl!(transfer copy);
+handler_impl!(transfer rename);
+handler_impl!(transfer export_inventory, ExportInventoryResponse);
+handler_impl!(capabilities);
 handler_impl!(presign_read, PresignResponse);
 handler_impl!(presign_stat, PresignResponse);
+handler_impl!(presign_write, PresignResponse);
+handler_impl!(presign_delete, PresignResponse);
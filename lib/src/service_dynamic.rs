@@ -41,6 +41,57 @@ macro_rules! handler_impl {
         }
     };
 
+    (batch $name:ident) => {
+        paste::paste! {
+            impl<F> ServiceImpl<F>
+            where
+                F: OperatorFactory,
+            {
+                async fn [<_ $name:snake>](&self, request: [<$name:camel Request>]) -> Result<[<$name:camel Response>], Error> {
+                    let mut results: Vec<_> = request.locations.iter().map(|_| None).collect();
+
+                    for (uri, indices, paths) in group_by_store(&request.locations) {
+                        let operator = self.factory.load(uri.as_str())?;
+
+                        let store_results = service::$name(&operator, paths, request.options.clone()).await?;
+
+                        for (i, result) in indices.into_iter().zip(store_results) {
+                            results[i] = Some(result);
+                        }
+                    }
+
+                    Ok(BatchResponse::new(request.locations, results.into_iter().flatten().collect()))
+                }
+            }
+        }
+    };
+
+    (transfer $name:ident, $response:ty) => {
+        paste::paste! {
+            impl<F> ServiceImpl<F>
+            where
+                F: OperatorFactory,
+            {
+                async fn [<_ $name:snake>](&self, request: [<$name:camel Request>]) -> Result<$response, Error> {
+                    let (source_uri, source_path) = parse_uri(request.source.clone());
+                    let (destination_uri, destination_path) = parse_uri(request.destination.clone());
+
+                    let source = self.factory.load(source_uri.as_str())?;
+                    let destination = self.factory.load(destination_uri.as_str())?;
+
+                    // Profiles of the same kind (eg. two S3 accounts) can only be told apart by their URI.
+                    let same_store = source_uri == destination_uri;
+
+                    service::$name(&source, source_path.as_str(), &destination, destination_path.as_str(), same_store, request).await
+                }
+            }
+        }
+    };
+
+    (transfer $name:ident) => {
+        handler_impl!(transfer $name, ());
+    };
+
     ($name:ident) => {
         paste::paste! {
             handler_impl!($name, [<$name:camel Response>]);
@@ -50,12 +101,37 @@ macro_rules! handler_impl {
 
 fn parse_uri(uri: Url) -> (String, String) {
     let mut uri = uri;
-    let path = uri.path().to_string();
+
+    // Like entry paths, location paths are relative to the store root (except for the root).
+    let path = match uri.path().trim_start_matches('/') {
+        "" => "/".to_string(),
+        path => path.to_string(),
+    };
+
     uri.set_path("");
 
     (uri.to_string(), path)
 }
 
+/// Group locations by the store they belong to, keeping track of their original position.
+fn group_by_store(locations: &[Url]) -> Vec<(String, Vec<usize>, Vec<String>)> {
+    let mut groups: Vec<(String, Vec<usize>, Vec<String>)> = Vec::new();
+
+    for (i, location) in locations.iter().enumerate() {
+        let (uri, path) = parse_uri(location.clone());
+
+        match groups.iter_mut().find(|(u, _, _)| *u == uri) {
+            Some((_, indices, paths)) => {
+                indices.push(i);
+                paths.push(path);
+            }
+            None => groups.push((uri, vec![i], vec![path])),
+        }
+    }
+
+    groups
+}
+
 include!("service_common.rs");
 
 impl<F> Service for ServiceImpl<F>
@@ -73,6 +149,225 @@ where
             .await?)
     }
 
+    /// List a single level of a given location, returning files and prefixes separately.
+    async fn list_delimited(
+        &self,
+        ctx: Context<'_>,
+        request: Json<ListDelimitedRequest>,
+    ) -> HandlerResult<Json<ListDelimitedResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._list_delimited(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// List a given location as a tree down to a given depth.
+    async fn tree(
+        &self,
+        ctx: Context<'_>,
+        request: Json<TreeRequest>,
+    ) -> HandlerResult<Json<TreeNode>> {
+        Ok(ctx
+            .run(async || Ok(self._tree(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Search entries under a given location by user metadata.
+    async fn search(
+        &self,
+        ctx: Context<'_>,
+        request: Json<SearchRequest>,
+    ) -> HandlerResult<Json<ListResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._search(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Summarize disk usage under a given location.
+    async fn usage(
+        &self,
+        ctx: Context<'_>,
+        request: Json<UsageRequest>,
+    ) -> HandlerResult<Json<UsageResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._usage(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Retrieve metadata of a given location.
+    async fn stat(
+        &self,
+        ctx: Context<'_>,
+        request: Json<StatRequest>,
+    ) -> HandlerResult<Json<Metadata>> {
+        Ok(ctx
+            .run(async || Ok(self._stat(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Retrieve metadata of a list of locations.
+    async fn stat_batch(
+        &self,
+        ctx: Context<'_>,
+        request: Json<StatBatchRequest>,
+    ) -> HandlerResult<Json<StatBatchResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._stat_batch(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Read the content of a given location inline.
+    async fn read(
+        &self,
+        ctx: Context<'_>,
+        request: Json<ReadRequest>,
+    ) -> HandlerResult<Json<ReadResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._read(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Compute digests of the content of a given location.
+    async fn checksum(
+        &self,
+        ctx: Context<'_>,
+        request: Json<ChecksumRequest>,
+    ) -> HandlerResult<Json<ChecksumResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._checksum(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Write inline content to a given location.
+    async fn write(
+        &self,
+        ctx: Context<'_>,
+        request: Json<WriteRequest>,
+    ) -> HandlerResult<Json<Metadata>> {
+        Ok(ctx
+            .run(async || Ok(self._write(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Append inline content to a given location.
+    async fn append(
+        &self,
+        ctx: Context<'_>,
+        request: Json<AppendRequest>,
+    ) -> HandlerResult<Json<AppendResponse>> {
+        let request = request.into_inner();
+        let location = request.location.clone();
+
+        // The stat is journaled on its own, so a retry never appends the content twice.
+        ctx.run(async || Ok(self._append(request).await?)).await?;
+
+        let Json(metadata) = ctx
+            .run(async || {
+                Ok(self
+                    ._stat(StatRequest {
+                        location,
+                        options: None,
+                    })
+                    .await
+                    .map(Json)?)
+            })
+            .await?;
+
+        Ok(Json(AppendResponse {
+            content_length: metadata.content_length.unwrap_or_default(),
+        }))
+    }
+
+    /// Delete a given location.
+    async fn delete(
+        &self,
+        ctx: Context<'_>,
+        request: Json<DeleteRequest>,
+    ) -> HandlerResult<Json<DeleteResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._delete(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Remove all entries under a given location recursively.
+    async fn remove_all(
+        &self,
+        ctx: Context<'_>,
+        request: Json<RemoveAllRequest>,
+    ) -> HandlerResult<Json<DeleteResponse>> {
+        let request = request.into_inner();
+        let batch_size = request.batch_size() as u64;
+        let mut deleted = 0;
+
+        // Every batch is journaled on its own, so a retry resumes with the remaining entries.
+        loop {
+            let Json(batch) = ctx
+                .run(async || Ok(self._remove_all(request.clone()).await.map(Json)?))
+                .await?;
+
+            deleted += batch.deleted;
+
+            if batch.deleted < batch_size {
+                break;
+            }
+        }
+
+        Ok(Json(DeleteResponse { deleted }))
+    }
+
+    /// Delete a list of locations.
+    async fn delete_batch(
+        &self,
+        ctx: Context<'_>,
+        request: Json<DeleteBatchRequest>,
+    ) -> HandlerResult<Json<DeleteBatchResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._delete_batch(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Copy a file within the store.
+    async fn copy(&self, ctx: Context<'_>, request: Json<CopyRequest>) -> HandlerResult<()> {
+        ctx.run(async || Ok(self._copy(request.into_inner()).await?))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Rename a file within the store.
+    async fn rename(&self, ctx: Context<'_>, request: Json<RenameRequest>) -> HandlerResult<()> {
+        ctx.run(async || Ok(self._rename(request.into_inner()).await?))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Export a recursive listing of a location into another location.
+    async fn export_inventory(
+        &self,
+        ctx: Context<'_>,
+        request: Json<ExportInventoryRequest>,
+    ) -> HandlerResult<Json<ExportInventoryResponse>> {
+        Ok(ctx
+            .run(async || {
+                Ok(self
+                    ._export_inventory(request.into_inner())
+                    .await
+                    .map(Json)?)
+            })
+            .await?)
+    }
+
+    /// Retrieve information and capabilities of the store behind a given location.
+    async fn capabilities(
+        &self,
+        ctx: Context<'_>,
+        request: Json<CapabilitiesRequest>,
+    ) -> HandlerResult<Json<CapabilitiesResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._capabilities(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
     /// Presign an operation for read.
     async fn presign_read(
         &self,
@@ -94,4 +389,53 @@ where
             .run(async || Ok(self._presign_stat(request.into_inner()).await.map(Json)?))
             .await?)
     }
+
+    /// Presign an operation for write.
+    async fn presign_write(
+        &self,
+        ctx: Context<'_>,
+        request: Json<PresignWriteRequest>,
+    ) -> HandlerResult<Json<PresignResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._presign_write(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Presign an operation for delete.
+    async fn presign_delete(
+        &self,
+        ctx: Context<'_>,
+        request: Json<PresignDeleteRequest>,
+    ) -> HandlerResult<Json<PresignResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._presign_delete(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_uri_paths() {
+        let parse = |uri: &str| parse_uri(Url::parse(uri).unwrap());
+
+        assert_eq!(
+            parse("s3://bucket/dir/a.txt"),
+            ("s3://bucket".to_string(), "dir/a.txt".to_string())
+        );
+        assert_eq!(
+            parse("s3://bucket/dir/"),
+            ("s3://bucket".to_string(), "dir/".to_string())
+        );
+        assert_eq!(
+            parse("s3://bucket/"),
+            ("s3://bucket".to_string(), "/".to_string())
+        );
+        assert_eq!(
+            parse("s3://bucket"),
+            ("s3://bucket".to_string(), "/".to_string())
+        );
+    }
 }
@@ -1,15 +1,40 @@
+use std::collections::HashMap;
+
+use content_disposition::parse_content_disposition;
+use futures::{StreamExt, TryStreamExt};
 use opendal::Operator;
 use opendal_util::{Copier, CopyOptions, OperatorFactory};
-use restate_sdk::prelude::*;
+use restate_sdk::{errors::TerminalError, prelude::*};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::{
+    error::Error,
+    service::{
+        self, DEFAULT_BATCH_CONCURRENCY, Entry, EntryMode, ErrorDetails, ListFilter, ListRequest,
+        Metadata, already_exists, copy_file,
+    },
+};
+
 #[restate_sdk::service]
 #[name = "OpenDALExtra"]
 pub trait Service {
     /// Copy a file from one location to another.
     async fn copy(request: Json<CopyRequest>) -> HandlerResult<()>;
+
+    /// Move a file from one location to another.
+    ///
+    /// The source is only deleted once the copy has been verified.
+    #[name = "move"]
+    async fn move_file(request: Json<MoveRequest>) -> HandlerResult<Json<Metadata>>;
+
+    /// Copy every file under a prefix to another location.
+    #[name = "copyPrefix"]
+    async fn copy_prefix(request: Json<CopyPrefixRequest>) -> HandlerResult<Json<CopySummary>>;
+
+    /// Synchronize a prefix to another location, copying only new or changed files.
+    async fn sync(request: Json<SyncRequest>) -> HandlerResult<Json<SyncResponse>>;
 }
 
 #[derive(Default)]
@@ -37,6 +62,22 @@ pub struct CopyRequest {
     pub destination: Url,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<CopyOptions>,
+    /// What to do when the destination already exists.
+    ///
+    /// Policies other than `overwrite` only support copying a single file.
+    #[serde(default)]
+    pub policy: OverwritePolicy,
+    /// How to set the metadata of the destination.
+    ///
+    /// Modes other than `preserve` only support copying a single file.
+    /// Directories and globs only preserve content types.
+    #[serde(default)]
+    pub metadata_mode: MetadataMode,
+    /// Metadata to set on the destination.
+    ///
+    /// Ignored in `preserve` mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CopyMetadata>,
 }
 
 fn example_copy_request() -> CopyRequest {
@@ -47,24 +88,848 @@ fn example_copy_request() -> CopyRequest {
         .unwrap(),
         destination: Url::parse("s3://bucket/bunny.mov").unwrap(),
         options: None,
+        policy: OverwritePolicy::SkipIfExists,
+        metadata_mode: MetadataMode::Merge,
+        metadata: Some(CopyMetadata {
+            cache_control: Some("max-age=86400".to_string()),
+            ..Default::default()
+        }),
+    }
+}
+
+/// Policy applied when the destination of a copy already exists.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum OverwritePolicy {
+    /// Replace the destination.
+    #[default]
+    Overwrite,
+    /// Fail the copy.
+    ///
+    /// Uses a conditional write if the destination store supports it.
+    FailIfExists,
+    /// Leave the destination untouched.
+    SkipIfExists,
+    /// Leave the destination untouched if it has the same size and ETag as the source.
+    SkipIfSameSizeAndEtag,
+}
+
+/// How to set the metadata of the destination of a copy.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum MetadataMode {
+    /// Copy metadata from the source.
+    ///
+    /// Metadata not supported by the destination store is dropped.
+    #[default]
+    Preserve,
+    /// Only set the supplied metadata.
+    Replace,
+    /// Copy metadata from the source, overridden by the supplied metadata.
+    ///
+    /// User metadata is merged key by key.
+    Merge,
+}
+
+/// Metadata set on the destination of a copy.
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyMetadata {
+    pub cache_control: Option<String>,
+    pub content_type: Option<String>,
+    pub content_disposition: Option<String>,
+    pub content_encoding: Option<String>,
+    pub user_metadata: Option<HashMap<String, String>>,
+}
+
+impl CopyMetadata {
+    /// Take metadata from the source that the destination store can store.
+    fn from_source(metadata: &opendal::Metadata, capability: opendal::Capability) -> Self {
+        let supported = |supported: bool, value: Option<&str>| {
+            value.filter(|_| supported).map(|s| s.to_string())
+        };
+
+        CopyMetadata {
+            cache_control: supported(
+                capability.write_with_cache_control,
+                metadata.cache_control(),
+            ),
+            content_type: supported(capability.write_with_content_type, metadata.content_type()),
+            content_disposition: supported(
+                capability.write_with_content_disposition,
+                metadata.content_disposition(),
+            ),
+            content_encoding: supported(
+                capability.write_with_content_encoding,
+                metadata.content_encoding(),
+            ),
+            user_metadata: metadata
+                .user_metadata()
+                .filter(|_| capability.write_with_user_metadata)
+                .cloned(),
+        }
+    }
+
+    fn merge(self, other: CopyMetadata) -> Self {
+        let user_metadata = match (self.user_metadata, other.user_metadata) {
+            (Some(mut user_metadata), Some(other)) => {
+                user_metadata.extend(other);
+
+                Some(user_metadata)
+            }
+            (user_metadata, other) => other.or(user_metadata),
+        };
+
+        CopyMetadata {
+            cache_control: other.cache_control.or(self.cache_control),
+            content_type: other.content_type.or(self.content_type),
+            content_disposition: other.content_disposition.or(self.content_disposition),
+            content_encoding: other.content_encoding.or(self.content_encoding),
+            user_metadata,
+        }
+    }
+}
+
+impl From<CopyMetadata> for opendal::options::WriteOptions {
+    fn from(metadata: CopyMetadata) -> Self {
+        Self {
+            cache_control: metadata.cache_control,
+            content_type: metadata.content_type,
+            content_disposition: metadata.content_disposition,
+            content_encoding: metadata.content_encoding,
+            user_metadata: metadata.user_metadata,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(example = example_move_request())]
+pub struct MoveRequest {
+    pub source: Url,
+    /// Destination file, or directory to move the file into (if it ends with a slash).
+    pub destination: Url,
+}
+
+fn example_move_request() -> MoveRequest {
+    MoveRequest {
+        source: Url::parse("s3://staging/bunny.mov").unwrap(),
+        destination: Url::parse("gcs://bucket/movies/").unwrap(),
     }
 }
 
+/// State of a move, recorded before copying.
+#[derive(Debug, Deserialize, Serialize)]
+struct MovePlan {
+    /// Resolved destination path.
+    destination: String,
+    size: u64,
+    etag: Option<String>,
+}
+
+/// Default number of files listed in a single step of a prefix copy.
+pub const DEFAULT_COPY_PAGE_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(example = example_copy_prefix_request())]
+pub struct CopyPrefixRequest {
+    /// Prefix to copy files from.
+    pub source: Url,
+    /// Prefix to copy files to.
+    pub destination: Url,
+    /// Skip files that already exist at the destination.
+    #[serde(default)]
+    pub skip_existing: bool,
+    /// Maximum number of files copied in parallel.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_CONCURRENCY`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// Maximum number of files listed in a single (journaled) step.
+    ///
+    /// Defaults to [`DEFAULT_COPY_PAGE_SIZE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
+}
+
+impl CopyPrefixRequest {
+    fn page_size(&self) -> usize {
+        self.page_size.unwrap_or(DEFAULT_COPY_PAGE_SIZE).max(1)
+    }
+}
+
+fn example_copy_prefix_request() -> CopyPrefixRequest {
+    CopyPrefixRequest {
+        source: Url::parse("s3://bucket/datasets/").unwrap(),
+        destination: Url::parse("gcs://bucket/datasets/").unwrap(),
+        skip_existing: false,
+        concurrency: None,
+        page_size: None,
+    }
+}
+
+/// A page of files listed under a prefix, recorded in the journal.
+#[derive(Debug, Deserialize, Serialize)]
+struct FilePage {
+    /// Paths relative to the prefix.
+    paths: Vec<String>,
+    next_cursor: Option<String>,
+}
+
+/// Summary of copying multiple files.
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CopySummary {
+    pub copied: u64,
+    pub skipped: u64,
+    pub failed: u64,
+    /// Number of bytes copied.
+    pub bytes: u64,
+    /// Files that could not be copied.
+    pub failures: Vec<CopyFailure>,
+}
+
+impl CopySummary {
+    fn add(&mut self, path: String, outcome: CopyOutcome) {
+        match outcome {
+            CopyOutcome::Copied { bytes } => {
+                self.copied += 1;
+                self.bytes += bytes;
+            }
+            CopyOutcome::Skipped => self.skipped += 1,
+            CopyOutcome::Failed { error } => {
+                self.failed += 1;
+                self.failures.push(CopyFailure { path, error });
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyFailure {
+    /// Path of the file relative to the source prefix.
+    pub path: String,
+    pub error: ErrorDetails,
+}
+
+/// Default number of actions returned by a sync.
+pub const DEFAULT_SYNC_MAX_ACTIONS: usize = 1000;
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(example = example_sync_request())]
+pub struct SyncRequest {
+    /// Prefix to synchronize files from.
+    pub source: Url,
+    /// Prefix to synchronize files to.
+    pub destination: Url,
+    /// How to decide whether a file has changed.
+    #[serde(default)]
+    pub compare: SyncCompare,
+    /// Delete files from the destination that do not exist at the source.
+    #[serde(default)]
+    pub delete_extraneous: bool,
+    /// Only plan the actions without performing them.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Maximum number of files compared, copied or deleted in parallel.
+    ///
+    /// Defaults to [`DEFAULT_BATCH_CONCURRENCY`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// Maximum number of files listed in a single (journaled) step.
+    ///
+    /// Defaults to [`DEFAULT_COPY_PAGE_SIZE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<usize>,
+    /// Maximum number of actions returned in the response.
+    ///
+    /// Defaults to [`DEFAULT_SYNC_MAX_ACTIONS`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_actions: Option<usize>,
+}
+
+impl SyncRequest {
+    fn concurrency(&self) -> usize {
+        self.concurrency.unwrap_or(DEFAULT_BATCH_CONCURRENCY).max(1)
+    }
+
+    fn page_size(&self) -> usize {
+        self.page_size.unwrap_or(DEFAULT_COPY_PAGE_SIZE).max(1)
+    }
+}
+
+fn example_sync_request() -> SyncRequest {
+    SyncRequest {
+        source: Url::parse("s3://bucket/datasets/").unwrap(),
+        destination: Url::parse("gcs://bucket/datasets/").unwrap(),
+        compare: SyncCompare::SizeAndModified,
+        delete_extraneous: true,
+        dry_run: true,
+        concurrency: None,
+        page_size: None,
+        max_actions: None,
+    }
+}
+
+/// Strategy for detecting changed files.
+///
+/// Files with a different size are always considered changed.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncCompare {
+    /// Only compare sizes.
+    Size,
+    /// Files with a different ETag are changed.
+    ///
+    /// Only useful when both stores compute ETags the same way.
+    /// Files without an ETag are considered changed.
+    SizeAndEtag,
+    /// Files modified at the source after the destination was written are changed.
+    ///
+    /// Files without a modification time are considered changed.
+    #[default]
+    SizeAndModified,
+}
+
+impl SyncCompare {
+    fn is_changed(self, source: &Metadata, destination: &Metadata) -> bool {
+        if source.content_length != destination.content_length {
+            return true;
+        }
+
+        match self {
+            SyncCompare::Size => false,
+            SyncCompare::SizeAndEtag => match (&source.etag, &destination.etag) {
+                (Some(source), Some(destination)) => source != destination,
+                _ => true,
+            },
+            SyncCompare::SizeAndModified => match (source.last_modified, destination.last_modified)
+            {
+                (Some(source), Some(destination)) => source > destination,
+                _ => true,
+            },
+        }
+    }
+}
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncResponse {
+    /// Number of actions planned (and performed unless running in dry-run mode).
+    pub planned: SyncCounts,
+    /// Actions planned, up to the maximum number of actions requested.
+    pub actions: Vec<SyncAction>,
+    /// Whether more actions were planned than returned.
+    pub truncated: bool,
+    /// Number of files deleted from the destination.
+    pub deleted: u64,
+    /// Summary of the copied files.
+    ///
+    /// Unchanged files are reported as skipped.
+    #[serde(flatten)]
+    pub summary: CopySummary,
+}
+
+impl SyncResponse {
+    fn plan(&mut self, actions: &[SyncAction], max_actions: usize) {
+        for action in actions {
+            match action.kind {
+                SyncActionKind::Create => self.planned.create += 1,
+                SyncActionKind::Update => self.planned.update += 1,
+                SyncActionKind::Delete => self.planned.delete += 1,
+            }
+
+            if self.actions.len() < max_actions {
+                self.actions.push(action.clone());
+            } else {
+                self.truncated = true;
+            }
+        }
+    }
+}
+
+/// Number of files per sync action.
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncCounts {
+    pub create: u64,
+    pub update: u64,
+    pub delete: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncAction {
+    /// Path of the file relative to the prefixes.
+    pub path: String,
+    pub kind: SyncActionKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncActionKind {
+    /// The file does not exist at the destination.
+    Create,
+    /// The file changed at the source.
+    Update,
+    /// The file does not exist at the source.
+    Delete,
+}
+
+/// Actions planned for a page of files, recorded in the journal.
+#[derive(Debug, Deserialize, Serialize)]
+struct SyncPlan {
+    actions: Vec<SyncAction>,
+    unchanged: u64,
+    next_cursor: Option<String>,
+}
+
+/// Outcome of copying a single file, recorded in the journal.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+enum CopyOutcome {
+    Copied { bytes: u64 },
+    Skipped,
+    Failed { error: ErrorDetails },
+}
+
 impl<F> ServiceImpl<F>
 where
     F: OperatorFactory,
 {
-    async fn _copy(&self, request: CopyRequest) -> opendal::Result<()> {
+    async fn _copy(&self, request: CopyRequest) -> Result<(), Error> {
         let (src_path, src_op) = self.parse_location(request.source)?;
         let (dst_path, dst_op) = self.parse_location(request.destination)?;
 
-        let copier = Copier::new(src_op, dst_op);
+        let options = request.options.unwrap_or_default();
+        let is_glob = !options.disable_glob && src_path.contains(['*', '?', '[', '{']);
+
+        let metadata = if is_glob {
+            None
+        } else {
+            Some(src_op.stat(&src_path).await?)
+        };
+
+        // Directories and globs are copied by the Copier, which only preserves content types.
+        let Some(metadata) = metadata.filter(|metadata| metadata.is_file()) else {
+            if request.policy != OverwritePolicy::Overwrite {
+                return Err(TerminalError::new_with_code(
+                    400,
+                    "only files can be copied with an overwrite policy",
+                )
+                .into());
+            }
+
+            if request.metadata_mode != MetadataMode::Preserve || request.metadata.is_some() {
+                return Err(TerminalError::new_with_code(
+                    400,
+                    "only files can be copied with custom metadata",
+                )
+                .into());
+            }
+
+            return Ok(Copier::new(src_op, dst_op)
+                .copy_options(src_path, dst_path, options)
+                .await?);
+        };
+
+        let dst_path = resolve_destination(&src_path, &metadata, &dst_op, dst_path).await?;
+
+        let preserved = CopyMetadata::from_source(&metadata, dst_op.info().full_capability());
+
+        let mut options: opendal::options::WriteOptions = match request.metadata_mode {
+            MetadataMode::Preserve => preserved,
+            MetadataMode::Replace => request.metadata.unwrap_or_default(),
+            MetadataMode::Merge => preserved.merge(request.metadata.unwrap_or_default()),
+        }
+        .into();
+
+        match request.policy {
+            OverwritePolicy::Overwrite => (),
+            OverwritePolicy::FailIfExists => {
+                if dst_op.info().full_capability().write_with_if_not_exists {
+                    options.if_not_exists = true;
+                } else if dst_op.exists(&dst_path).await? {
+                    return Err(already_exists(&dst_path));
+                }
+            }
+            OverwritePolicy::SkipIfExists => {
+                if dst_op.exists(&dst_path).await? {
+                    return Ok(());
+                }
+            }
+            OverwritePolicy::SkipIfSameSizeAndEtag => match dst_op.stat(&dst_path).await {
+                Ok(existing)
+                    if existing.content_length() == metadata.content_length()
+                        && existing.etag().is_some()
+                        && existing.etag() == metadata.etag() =>
+                {
+                    return Ok(());
+                }
+                Ok(_) => (),
+                Err(err) if err.kind() == opendal::ErrorKind::NotFound => (),
+                Err(err) => return Err(err.into()),
+            },
+        }
+
+        copy_file(&src_op, &src_path, &dst_op, &dst_path, options).await
+    }
+
+    async fn _prepare_move(&self, request: MoveRequest) -> Result<MovePlan, Error> {
+        let (src_path, src_op) = self.parse_location(request.source.clone())?;
+        let (dst_path, dst_op) = self.parse_location(request.destination.clone())?;
+
+        if !src_op.info().full_capability().delete {
+            return Err(TerminalError::new_with_code(
+                501,
+                format!("{} does not support delete", src_op.info().scheme()),
+            )
+            .into());
+        }
+
+        let metadata = src_op.stat(&src_path).await?;
+
+        if !metadata.is_file() {
+            return Err(TerminalError::new_with_code(400, "source must be a file").into());
+        }
+
+        let destination = resolve_destination(&src_path, &metadata, &dst_op, dst_path).await?;
+
+        // Moving a file onto itself would delete it.
+        if is_same_store(&request.source, &request.destination)
+            && src_path.trim_start_matches('/') == destination.trim_start_matches('/')
+        {
+            return Err(TerminalError::new_with_code(
+                400,
+                "source and destination must be different",
+            )
+            .into());
+        }
+
+        Ok(MovePlan {
+            destination,
+            size: metadata.content_length(),
+            etag: metadata.etag().map(|s| s.to_string()),
+        })
+    }
+
+    async fn _copy_file(&self, request: MoveRequest, plan: &MovePlan) -> Result<(), Error> {
+        let (src_path, src_op) = self.parse_location(request.source)?;
+        let (_, dst_op) = self.parse_location(request.destination)?;
+
+        Copier::new(src_op, dst_op)
+            .copy_options(
+                src_path,
+                plan.destination.as_str(),
+                CopyOptions {
+                    recursive: false,
+                    disable_glob: true,
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn _verify_move(&self, request: MoveRequest, plan: &MovePlan) -> Result<Metadata, Error> {
+        let (src_path, src_op) = self.parse_location(request.source)?;
+        let (_, dst_op) = self.parse_location(request.destination)?;
+
+        let metadata = dst_op.stat(&plan.destination).await?;
+
+        // Some stores (eg. HTTP) may not report the size of the source.
+        if plan.size > 0 && metadata.content_length() != plan.size {
+            return Err(TerminalError::new_with_code(
+                500,
+                format!(
+                    "destination size {} does not match source size {}",
+                    metadata.content_length(),
+                    plan.size
+                ),
+            )
+            .into());
+        }
+
+        // Make sure the source was not modified while copying.
+        let source = src_op.stat(&src_path).await?;
+
+        if plan.etag.is_some() && source.etag() != plan.etag.as_deref() {
+            return Err(
+                TerminalError::new_with_code(409, "source changed while being moved").into(),
+            );
+        }
+
+        Ok(metadata.into())
+    }
+
+    async fn _delete_source(&self, request: MoveRequest) -> Result<(), Error> {
+        let (src_path, src_op) = self.parse_location(request.source)?;
+
+        src_op.delete(&src_path).await?;
+
+        Ok(())
+    }
+
+    async fn _list_page(
+        &self,
+        location: Url,
+        page_size: usize,
+        cursor: Option<String>,
+    ) -> Result<FilePage, Error> {
+        let (files, next_cursor) = self.list_files(location, page_size, cursor).await?;
+
+        Ok(FilePage {
+            paths: files.into_iter().map(|file| file.path).collect(),
+            next_cursor,
+        })
+    }
+
+    /// List a page of files under a prefix recursively, with paths relative to the prefix.
+    async fn list_files(
+        &self,
+        location: Url,
+        page_size: usize,
+        cursor: Option<String>,
+    ) -> Result<(Vec<Entry>, Option<String>), Error> {
+        let (path, op) = self.parse_location(location)?;
+        let prefix = prefix_path(&path);
+
+        let response = service::list(
+            &op,
+            &prefix,
+            ListRequest {
+                location: prefix.clone(),
+                options: Some(opendal::options::ListOptions {
+                    recursive: true,
+                    ..Default::default()
+                }),
+                page_size: Some(page_size),
+                cursor,
+                filter: Some(ListFilter {
+                    modes: vec![EntryMode::File],
+                    ..Default::default()
+                }),
+                sort: None,
+                max_entries: None,
+                stat: false,
+                stat_concurrency: None,
+            },
+        )
+        .await?;
+
+        let files = response
+            .entries
+            .into_iter()
+            .map(|entry| Entry {
+                path: match entry.path.strip_prefix(prefix.as_str()) {
+                    Some(relative) => relative.to_string(),
+                    None => entry.path,
+                },
+                metadata: entry.metadata,
+            })
+            .collect();
+
+        Ok((files, response.next_cursor))
+    }
+
+    /// Plan copying a page of source files, comparing them to the destination.
+    async fn _plan_sync(
+        &self,
+        request: &SyncRequest,
+        cursor: Option<String>,
+    ) -> Result<SyncPlan, Error> {
+        let (src_path, src_op) = self.parse_location(request.source.clone())?;
+        let (dst_path, dst_op) = self.parse_location(request.destination.clone())?;
+        let (src_prefix, dst_prefix) = (prefix_path(&src_path), prefix_path(&dst_path));
+        let compare = request.compare;
+
+        let (files, next_cursor) = self
+            .list_files(request.source.clone(), request.page_size(), cursor)
+            .await?;
+
+        let kinds: Vec<(String, Option<SyncActionKind>)> = futures::stream::iter(files)
+            .map(|file| {
+                let (src_op, dst_op) = (src_op.clone(), dst_op.clone());
+                let src_path = format!("{src_prefix}{}", file.path);
+                let dst_path = format!("{dst_prefix}{}", file.path);
+
+                async move {
+                    // Listings do not always include sizes or modification times.
+                    let source = match file.metadata {
+                        metadata
+                            if metadata.content_length.unwrap_or_default() == 0
+                                || metadata.last_modified.is_none() =>
+                        {
+                            src_op.stat(&src_path).await?.into()
+                        }
+                        metadata => metadata,
+                    };
+
+                    let kind = match dst_op.stat(&dst_path).await.map(Metadata::from) {
+                        Ok(existing) if compare.is_changed(&source, &existing) => {
+                            Some(SyncActionKind::Update)
+                        }
+                        Ok(_) => None,
+                        Err(err) if err.kind() == opendal::ErrorKind::NotFound => {
+                            Some(SyncActionKind::Create)
+                        }
+                        Err(err) => return Err(err),
+                    };
+
+                    Ok((file.path, kind))
+                }
+            })
+            .buffered(request.concurrency())
+            .try_collect()
+            .await?;
+
+        let mut plan = SyncPlan {
+            actions: Vec::new(),
+            unchanged: 0,
+            next_cursor,
+        };
+
+        for (path, kind) in kinds {
+            match kind {
+                Some(kind) => plan.actions.push(SyncAction { path, kind }),
+                None => plan.unchanged += 1,
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Plan deleting a page of destination files that do not exist at the source.
+    async fn _plan_deletes(
+        &self,
+        request: &SyncRequest,
+        cursor: Option<String>,
+    ) -> Result<SyncPlan, Error> {
+        let (src_path, src_op) = self.parse_location(request.source.clone())?;
+        let src_prefix = prefix_path(&src_path);
+
+        let (files, next_cursor) = self
+            .list_files(request.destination.clone(), request.page_size(), cursor)
+            .await?;
+
+        let extraneous: Vec<Option<String>> = futures::stream::iter(files)
+            .map(|file| {
+                let src_op = src_op.clone();
+                let src_path = format!("{src_prefix}{}", file.path);
+
+                async move {
+                    let exists = src_op.exists(&src_path).await?;
+
+                    Ok::<_, opendal::Error>((!exists).then_some(file.path))
+                }
+            })
+            .buffered(request.concurrency())
+            .try_collect()
+            .await?;
+
+        Ok(SyncPlan {
+            actions: extraneous
+                .into_iter()
+                .flatten()
+                .map(|path| SyncAction {
+                    path,
+                    kind: SyncActionKind::Delete,
+                })
+                .collect(),
+            unchanged: 0,
+            next_cursor,
+        })
+    }
+
+    /// Delete files relative to a prefix concurrently.
+    async fn _delete_chunk(
+        &self,
+        location: &Url,
+        paths: &[String],
+    ) -> Result<Vec<Option<ErrorDetails>>, Error> {
+        futures::future::try_join_all(
+            paths
+                .iter()
+                .map(|path| self._delete_relative(location, path)),
+        )
+        .await
+    }
+
+    /// Delete a file relative to a prefix, returning permanent errors instead of failing.
+    async fn _delete_relative(
+        &self,
+        location: &Url,
+        path: &str,
+    ) -> Result<Option<ErrorDetails>, Error> {
+        let (prefix, op) = self.parse_location(location.clone())?;
+
+        match op.delete(&format!("{}{path}", prefix_path(&prefix))).await {
+            Ok(()) => Ok(None),
+            Err(err) if err.is_permanent() => Ok(Some(err.into())),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Copy files relative to a prefix concurrently.
+    async fn _copy_chunk(
+        &self,
+        source: &Url,
+        destination: &Url,
+        paths: &[String],
+        skip_existing: bool,
+    ) -> Result<Vec<CopyOutcome>, Error> {
+        futures::future::try_join_all(
+            paths
+                .iter()
+                .map(|path| self._copy_relative(source, destination, path, skip_existing)),
+        )
+        .await
+    }
+
+    async fn _copy_relative(
+        &self,
+        source: &Url,
+        destination: &Url,
+        path: &str,
+        skip_existing: bool,
+    ) -> Result<CopyOutcome, Error> {
+        let (src_path, src_op) = self.parse_location(source.clone())?;
+        let (dst_path, dst_op) = self.parse_location(destination.clone())?;
 
-        if let Some(options) = request.options {
-            return copier.copy_options(src_path, dst_path, options).await;
+        let src_path = format!("{}{path}", prefix_path(&src_path));
+        let dst_path = format!("{}{path}", prefix_path(&dst_path));
+
+        if skip_existing && dst_op.exists(&dst_path).await? {
+            return Ok(CopyOutcome::Skipped);
+        }
+
+        let copier = Copier::new(src_op, dst_op.clone());
+        let options = CopyOptions {
+            recursive: false,
+            disable_glob: true,
+        };
+
+        match copier
+            .copy_options(src_path, dst_path.as_str(), options)
+            .await
+        {
+            Ok(()) => (),
+            // Permanent errors are reported in the summary, temporary ones are retried.
+            Err(err) if err.is_permanent() => {
+                return Ok(CopyOutcome::Failed { error: err.into() });
+            }
+            Err(err) => return Err(err.into()),
         }
 
-        copier.copy(src_path, dst_path).await
+        let metadata = dst_op.stat(&dst_path).await?;
+
+        Ok(CopyOutcome::Copied {
+            bytes: metadata.content_length(),
+        })
     }
 
     fn parse_location(&self, location: Url) -> opendal::Result<(String, Operator)> {
@@ -89,4 +954,616 @@ where
 
         Ok(())
     }
+
+    /// Move a file from one location to another.
+    ///
+    /// The source is only deleted once the copy has been verified.
+    async fn move_file(
+        &self,
+        ctx: Context<'_>,
+        request: Json<MoveRequest>,
+    ) -> HandlerResult<Json<Metadata>> {
+        let request = request.into_inner();
+
+        let plan = ctx
+            .run(async || Ok(self._prepare_move(request.clone()).await.map(Json)?))
+            .await?
+            .into_inner();
+
+        ctx.run(async || Ok(self._copy_file(request.clone(), &plan).await?))
+            .await?;
+
+        let metadata = ctx
+            .run(async || Ok(self._verify_move(request.clone(), &plan).await.map(Json)?))
+            .await?;
+
+        ctx.run(async || Ok(self._delete_source(request.clone()).await?))
+            .await?;
+
+        Ok(metadata)
+    }
+
+    /// Copy every file under a prefix to another location.
+    async fn copy_prefix(
+        &self,
+        ctx: Context<'_>,
+        request: Json<CopyPrefixRequest>,
+    ) -> HandlerResult<Json<CopySummary>> {
+        let request = request.into_inner();
+
+        // Files copied into the source would be listed (and copied) again.
+        if is_overlapping(&request.source, &request.destination) {
+            return Err(TerminalError::new_with_code(
+                400,
+                "source and destination prefixes must not overlap",
+            )
+            .into());
+        }
+        let concurrency = request
+            .concurrency
+            .unwrap_or(DEFAULT_BATCH_CONCURRENCY)
+            .max(1);
+        let page_size = request.page_size();
+
+        let mut summary = CopySummary::default();
+        let mut cursor = None;
+
+        // Pages are listed one step at a time and copied before listing the next one.
+        // Files are copied concurrently in chunks, each chunk being a step on its own,
+        // so a restart resumes with the first chunk that did not complete.
+        loop {
+            let page = ctx
+                .run(async || {
+                    Ok(self
+                        ._list_page(request.source.clone(), page_size, cursor.clone())
+                        .await
+                        .map(Json)?)
+                })
+                .await?
+                .into_inner();
+
+            for chunk in page.paths.chunks(concurrency) {
+                let outcomes = ctx
+                    .run(async || {
+                        Ok(self
+                            ._copy_chunk(
+                                &request.source,
+                                &request.destination,
+                                chunk,
+                                request.skip_existing,
+                            )
+                            .await
+                            .map(Json)?)
+                    })
+                    .await?
+                    .into_inner();
+
+                for (path, outcome) in chunk.iter().zip(outcomes) {
+                    summary.add(path.clone(), outcome);
+                }
+            }
+
+            cursor = page.next_cursor;
+
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        Ok(Json(summary))
+    }
+
+    /// Synchronize a prefix to another location, copying only new or changed files.
+    async fn sync(
+        &self,
+        ctx: Context<'_>,
+        request: Json<SyncRequest>,
+    ) -> HandlerResult<Json<SyncResponse>> {
+        let request = request.into_inner();
+
+        // Files copied into the source would be compared (and copied) again.
+        if is_overlapping(&request.source, &request.destination) {
+            return Err(TerminalError::new_with_code(
+                400,
+                "source and destination prefixes must not overlap",
+            )
+            .into());
+        }
+
+        let concurrency = request.concurrency();
+        let max_actions = request.max_actions.unwrap_or(DEFAULT_SYNC_MAX_ACTIONS);
+
+        let mut response = SyncResponse::default();
+        let mut cursor = None;
+
+        // Source files are compared and copied one page at a time.
+        // Each chunk of copies is a step on its own, like in copyPrefix.
+        loop {
+            let plan = ctx
+                .run(async || Ok(self._plan_sync(&request, cursor.clone()).await.map(Json)?))
+                .await?
+                .into_inner();
+
+            response.plan(&plan.actions, max_actions);
+            response.summary.skipped += plan.unchanged;
+
+            if !request.dry_run {
+                let paths: Vec<String> =
+                    plan.actions.into_iter().map(|action| action.path).collect();
+
+                for chunk in paths.chunks(concurrency) {
+                    let outcomes = ctx
+                        .run(async || {
+                            Ok(self
+                                ._copy_chunk(&request.source, &request.destination, chunk, false)
+                                .await
+                                .map(Json)?)
+                        })
+                        .await?
+                        .into_inner();
+
+                    for (path, outcome) in chunk.iter().zip(outcomes) {
+                        response.summary.add(path.clone(), outcome);
+                    }
+                }
+            }
+
+            cursor = plan.next_cursor;
+
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        if !request.delete_extraneous {
+            return Ok(Json(response));
+        }
+
+        // Deleting files while listing the destination would shift the pages,
+        // so every page is planned before deleting anything.
+        let mut deletes = Vec::new();
+
+        loop {
+            let plan = ctx
+                .run(async || {
+                    Ok(self
+                        ._plan_deletes(&request, cursor.clone())
+                        .await
+                        .map(Json)?)
+                })
+                .await?
+                .into_inner();
+
+            response.plan(&plan.actions, max_actions);
+            deletes.extend(plan.actions.into_iter().map(|action| action.path));
+
+            cursor = plan.next_cursor;
+
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        if request.dry_run {
+            return Ok(Json(response));
+        }
+
+        for chunk in deletes.chunks(concurrency) {
+            let outcomes = ctx
+                .run(async || {
+                    Ok(self
+                        ._delete_chunk(&request.destination, chunk)
+                        .await
+                        .map(Json)?)
+                })
+                .await?
+                .into_inner();
+
+            for (path, outcome) in chunk.iter().zip(outcomes) {
+                match outcome {
+                    None => response.deleted += 1,
+                    Some(error) => {
+                        response.summary.failed += 1;
+                        response.summary.failures.push(CopyFailure {
+                            path: path.clone(),
+                            error,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(Json(response))
+    }
+}
+
+/// Whether two locations are in the same store (ie. they only differ in their paths).
+fn is_same_store(a: &Url, b: &Url) -> bool {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    a.set_path("");
+    b.set_path("");
+
+    a == b
+}
+
+/// Whether one of two prefixes in the same store contains the other.
+fn is_overlapping(a: &Url, b: &Url) -> bool {
+    let (a_prefix, b_prefix) = (prefix_path(a.path()), prefix_path(b.path()));
+
+    is_same_store(a, b) && (a_prefix.starts_with(&b_prefix) || b_prefix.starts_with(&a_prefix))
+}
+
+/// Normalize a path into a prefix that file names can be appended to.
+fn prefix_path(path: &str) -> String {
+    let path = path.trim_matches('/');
+
+    if path.is_empty() {
+        String::new()
+    } else {
+        format!("{path}/")
+    }
+}
+
+/// Resolve the destination of a file copy: copying into a directory keeps the file name.
+async fn resolve_destination(
+    src_path: &str,
+    src_metadata: &opendal::Metadata,
+    dst_op: &Operator,
+    dst_path: String,
+) -> Result<String, Error> {
+    let is_dir = dst_path.ends_with('/')
+        || match dst_op.stat(&dst_path).await {
+            Ok(metadata) => metadata.is_dir(),
+            Err(err) if err.kind() == opendal::ErrorKind::NotFound => false,
+            Err(err) => return Err(err.into()),
+        };
+
+    if !is_dir {
+        return Ok(dst_path);
+    }
+
+    // Like the Copier, fall back to the file name advertised by the source (eg. HTTP downloads).
+    let name = src_path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string())
+        .or_else(|| {
+            src_metadata
+                .content_disposition()
+                .and_then(|cd| parse_content_disposition(cd).filename_full())
+        });
+
+    let Some(name) = name else {
+        return Err(TerminalError::new_with_code(400, "source has no file name").into());
+    };
+
+    Ok(format!("{}/{name}", dst_path.trim_end_matches('/')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hands out the same in-memory store for every location.
+    struct MemoryFactory(Operator);
+
+    impl OperatorFactory for MemoryFactory {
+        fn load(&self, _uri: &str) -> opendal::Result<Operator> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn memory() -> (Operator, ServiceImpl<MemoryFactory>) {
+        let operator = Operator::new(opendal::services::Memory::default())
+            .unwrap()
+            .finish();
+
+        (operator.clone(), ServiceImpl::new(MemoryFactory(operator)))
+    }
+
+    fn location(uri: &str) -> Url {
+        Url::parse(uri).unwrap()
+    }
+
+    #[tokio::test]
+    async fn sync_plans() {
+        let (operator, service) = memory();
+
+        operator.write("src/new.txt", "hello").await.unwrap();
+        operator.write("src/changed.txt", "hello").await.unwrap();
+        operator.write("src/same.txt", "hello").await.unwrap();
+        operator.write("dst/changed.txt", "hi").await.unwrap();
+        operator.write("dst/same.txt", "world").await.unwrap();
+        operator.write("dst/extra.txt", "hi").await.unwrap();
+
+        let request = SyncRequest {
+            source: location("memory://s/src/"),
+            destination: location("memory://s/dst/"),
+            compare: SyncCompare::Size,
+            page_size: Some(2),
+            ..example_sync_request()
+        };
+
+        let mut actions = Vec::new();
+        let mut unchanged = 0;
+        let mut cursor = None;
+
+        loop {
+            let plan = service._plan_sync(&request, cursor).await.unwrap();
+
+            actions.extend(plan.actions);
+            unchanged += plan.unchanged;
+            cursor = plan.next_cursor;
+
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        loop {
+            let plan = service._plan_deletes(&request, cursor).await.unwrap();
+
+            actions.extend(plan.actions);
+            cursor = plan.next_cursor;
+
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let mut actions: Vec<_> = actions
+            .into_iter()
+            .map(|action| (action.path, action.kind))
+            .collect();
+        actions.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(unchanged, 1);
+        assert_eq!(
+            actions,
+            [
+                ("changed.txt".to_string(), SyncActionKind::Update),
+                ("extra.txt".to_string(), SyncActionKind::Delete),
+                ("new.txt".to_string(), SyncActionKind::Create),
+            ]
+        );
+    }
+
+    #[test]
+    fn sync_response_caps_actions() {
+        let mut response = SyncResponse::default();
+        let action = |path: &str, kind| SyncAction {
+            path: path.to_string(),
+            kind,
+        };
+
+        response.plan(
+            &[
+                action("a.txt", SyncActionKind::Create),
+                action("b.txt", SyncActionKind::Update),
+                action("c.txt", SyncActionKind::Delete),
+            ],
+            2,
+        );
+
+        assert_eq!(response.actions.len(), 2);
+        assert!(response.truncated);
+        assert_eq!(response.planned.create, 1);
+        assert_eq!(response.planned.update, 1);
+        assert_eq!(response.planned.delete, 1);
+    }
+
+    #[test]
+    fn overlapping_prefixes() {
+        let overlapping = |a: &str, b: &str| is_overlapping(&location(a), &location(b));
+
+        assert!(overlapping("s3://b/data/", "s3://b/data/backup/"));
+        assert!(overlapping("s3://b/data/backup", "s3://b/data"));
+        assert!(overlapping("s3://b/data/", "s3://b/data"));
+        assert!(overlapping("s3://b/", "s3://b/data/"));
+        assert!(!overlapping("s3://b/data/", "s3://b/database/"));
+        assert!(!overlapping("s3://b/data/", "s3://c/data/backup/"));
+    }
+
+    #[tokio::test]
+    async fn copy_chunk_outcomes() {
+        let (operator, service) = memory();
+
+        operator.write("src/a.txt", "hello").await.unwrap();
+        operator.write("src/sub/b.txt", "hi").await.unwrap();
+        operator.write("dst/sub/b.txt", "old").await.unwrap();
+
+        let (source, destination) = (location("memory://s/src/"), location("memory://s/dst/"));
+        let paths = [
+            "a.txt".to_string(),
+            "sub/b.txt".to_string(),
+            "c.txt".to_string(),
+        ];
+
+        let outcomes = service
+            ._copy_chunk(&source, &destination, &paths, true)
+            .await
+            .unwrap();
+
+        assert!(matches!(outcomes[0], CopyOutcome::Copied { bytes: 5 }));
+        assert!(matches!(outcomes[1], CopyOutcome::Skipped));
+        assert!(matches!(outcomes[2], CopyOutcome::Failed { .. }));
+        assert_eq!(operator.read("dst/a.txt").await.unwrap().to_vec(), b"hello");
+        assert_eq!(
+            operator.read("dst/sub/b.txt").await.unwrap().to_vec(),
+            b"old"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_destination_file_names() {
+        let operator = Operator::new(opendal::services::Memory::default())
+            .unwrap()
+            .finish();
+        let metadata = opendal::Metadata::new(opendal::EntryMode::FILE);
+
+        let resolve = |src_path: &'static str, metadata: opendal::Metadata| {
+            let operator = operator.clone();
+
+            async move {
+                resolve_destination(src_path, &metadata, &operator, "/backup/".to_string()).await
+            }
+        };
+
+        assert_eq!(
+            resolve("/dir/a.txt", metadata.clone()).await.unwrap(),
+            "/backup/a.txt"
+        );
+        assert_eq!(
+            resolve("/download/", metadata.clone()).await.unwrap(),
+            "/backup/download"
+        );
+        assert!(resolve("/", metadata.clone()).await.is_err());
+
+        let metadata =
+            metadata.with_content_disposition("attachment; filename=\"a.txt\"".to_string());
+
+        assert_eq!(resolve("/", metadata).await.unwrap(), "/backup/a.txt");
+    }
+
+    #[test]
+    fn copy_metadata_merge() {
+        let source = CopyMetadata {
+            cache_control: Some("no-cache".to_string()),
+            content_type: Some("text/plain".to_string()),
+            user_metadata: Some(HashMap::from([
+                ("owner".to_string(), "alice".to_string()),
+                ("team".to_string(), "data".to_string()),
+            ])),
+            ..Default::default()
+        };
+
+        let merged = source.merge(CopyMetadata {
+            content_type: Some("text/csv".to_string()),
+            user_metadata: Some(HashMap::from([("owner".to_string(), "bob".to_string())])),
+            ..Default::default()
+        });
+
+        assert_eq!(merged.cache_control.as_deref(), Some("no-cache"));
+        assert_eq!(merged.content_type.as_deref(), Some("text/csv"));
+        assert_eq!(merged.content_disposition, None);
+        assert_eq!(
+            merged.user_metadata,
+            Some(HashMap::from([
+                ("owner".to_string(), "bob".to_string()),
+                ("team".to_string(), "data".to_string()),
+            ]))
+        );
+    }
+    #[tokio::test]
+    async fn copy_overwrite_policies() {
+        let (operator, service) = memory();
+
+        operator.write("src/a.txt", "new").await.unwrap();
+
+        let copy = |source: &str, policy| {
+            service._copy(CopyRequest {
+                source: location(source),
+                destination: location("memory://s/dst/a.txt"),
+                options: None,
+                policy,
+                metadata_mode: MetadataMode::Preserve,
+                metadata: None,
+            })
+        };
+        let read = || async { operator.read("dst/a.txt").await.unwrap().to_vec() };
+
+        operator.write("dst/a.txt", "old").await.unwrap();
+
+        assert!(
+            copy("memory://s/src/a.txt", OverwritePolicy::FailIfExists)
+                .await
+                .is_err()
+        );
+        assert_eq!(read().await, b"old");
+
+        copy("memory://s/src/a.txt", OverwritePolicy::SkipIfExists)
+            .await
+            .unwrap();
+        assert_eq!(read().await, b"old");
+
+        // The in-memory store reports no etags, so files of the same size are still copied.
+        copy(
+            "memory://s/src/a.txt",
+            OverwritePolicy::SkipIfSameSizeAndEtag,
+        )
+        .await
+        .unwrap();
+        assert_eq!(read().await, b"new");
+
+        operator.write("dst/a.txt", "older").await.unwrap();
+
+        copy("memory://s/src/a.txt", OverwritePolicy::Overwrite)
+            .await
+            .unwrap();
+        assert_eq!(read().await, b"new");
+
+        operator.delete("dst/a.txt").await.unwrap();
+
+        copy("memory://s/src/a.txt", OverwritePolicy::FailIfExists)
+            .await
+            .unwrap();
+        assert_eq!(read().await, b"new");
+
+        assert!(
+            copy("memory://s/src/*.txt", OverwritePolicy::SkipIfExists)
+                .await
+                .is_err()
+        );
+    }
+    #[tokio::test]
+    async fn move_file() {
+        let (operator, service) = memory();
+
+        operator.write("src/a.txt", "hello").await.unwrap();
+
+        let request = || MoveRequest {
+            source: location("memory://s/src/a.txt"),
+            destination: location("memory://s/dst/"),
+        };
+
+        let plan = service._prepare_move(request()).await.unwrap();
+
+        assert_eq!(plan.destination, "/dst/a.txt");
+
+        service._copy_file(request(), &plan).await.unwrap();
+        service._verify_move(request(), &plan).await.unwrap();
+        service._delete_source(request()).await.unwrap();
+
+        assert!(!operator.exists("src/a.txt").await.unwrap());
+        assert_eq!(operator.read("dst/a.txt").await.unwrap().to_vec(), b"hello");
+    }
+
+    #[tokio::test]
+    async fn move_file_verification() {
+        let (operator, service) = memory();
+
+        operator.write("src/a.txt", "hello").await.unwrap();
+
+        // Moving a file onto itself would delete it.
+        let onto_itself = MoveRequest {
+            source: location("memory://s/src/a.txt"),
+            destination: location("memory://s/src/"),
+        };
+
+        assert!(service._prepare_move(onto_itself).await.is_err());
+
+        let request = || MoveRequest {
+            source: location("memory://s/src/a.txt"),
+            destination: location("memory://s/dst/"),
+        };
+
+        let plan = service._prepare_move(request()).await.unwrap();
+
+        // The destination does not match the source.
+        operator.write("dst/a.txt", "hi").await.unwrap();
+
+        assert!(service._verify_move(request(), &plan).await.is_err());
+    }
 }
@@ -27,6 +27,35 @@ macro_rules! handler_impl {
         }
     };
 
+    (batch $name:ident) => {
+        paste::paste! {
+            impl ServiceImpl {
+                async fn [<_ $name:snake>](&self, request: [<$name:camel Request>]) -> Result<[<$name:camel Response>], Error> {
+                    let results = service::$name(&self.operator, request.locations.clone(), request.options).await?;
+
+                    Ok(BatchResponse::new(request.locations, results))
+                }
+            }
+        }
+    };
+
+    (transfer $name:ident, $response:ty) => {
+        paste::paste! {
+            impl ServiceImpl {
+                async fn [<_ $name:snake>](&self, request: [<$name:camel Request>]) -> Result<$response, Error> {
+                    let source = request.source.clone();
+                    let destination = request.destination.clone();
+
+                    service::$name(&self.operator, source.as_str(), &self.operator, destination.as_str(), true, request).await
+                }
+            }
+        }
+    };
+
+    (transfer $name:ident) => {
+        handler_impl!(transfer $name, ());
+    };
+
     ($name:ident) => {
         paste::paste! {
             handler_impl!($name, [<$name:camel Response>]);
@@ -48,6 +77,225 @@ impl Service for ServiceImpl {
             .await?)
     }
 
+    /// List a single level of a given location, returning files and prefixes separately.
+    async fn list_delimited(
+        &self,
+        ctx: Context<'_>,
+        request: Json<ListDelimitedRequest>,
+    ) -> HandlerResult<Json<ListDelimitedResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._list_delimited(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// List a given location as a tree down to a given depth.
+    async fn tree(
+        &self,
+        ctx: Context<'_>,
+        request: Json<TreeRequest>,
+    ) -> HandlerResult<Json<TreeNode>> {
+        Ok(ctx
+            .run(async || Ok(self._tree(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Search entries under a given location by user metadata.
+    async fn search(
+        &self,
+        ctx: Context<'_>,
+        request: Json<SearchRequest>,
+    ) -> HandlerResult<Json<ListResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._search(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Summarize disk usage under a given location.
+    async fn usage(
+        &self,
+        ctx: Context<'_>,
+        request: Json<UsageRequest>,
+    ) -> HandlerResult<Json<UsageResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._usage(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Retrieve metadata of a given location.
+    async fn stat(
+        &self,
+        ctx: Context<'_>,
+        request: Json<StatRequest>,
+    ) -> HandlerResult<Json<Metadata>> {
+        Ok(ctx
+            .run(async || Ok(self._stat(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Retrieve metadata of a list of locations.
+    async fn stat_batch(
+        &self,
+        ctx: Context<'_>,
+        request: Json<StatBatchRequest>,
+    ) -> HandlerResult<Json<StatBatchResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._stat_batch(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Read the content of a given location inline.
+    async fn read(
+        &self,
+        ctx: Context<'_>,
+        request: Json<ReadRequest>,
+    ) -> HandlerResult<Json<ReadResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._read(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Compute digests of the content of a given location.
+    async fn checksum(
+        &self,
+        ctx: Context<'_>,
+        request: Json<ChecksumRequest>,
+    ) -> HandlerResult<Json<ChecksumResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._checksum(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Write inline content to a given location.
+    async fn write(
+        &self,
+        ctx: Context<'_>,
+        request: Json<WriteRequest>,
+    ) -> HandlerResult<Json<Metadata>> {
+        Ok(ctx
+            .run(async || Ok(self._write(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Append inline content to a given location.
+    async fn append(
+        &self,
+        ctx: Context<'_>,
+        request: Json<AppendRequest>,
+    ) -> HandlerResult<Json<AppendResponse>> {
+        let request = request.into_inner();
+        let location = request.location.clone();
+
+        // The stat is journaled on its own, so a retry never appends the content twice.
+        ctx.run(async || Ok(self._append(request).await?)).await?;
+
+        let Json(metadata) = ctx
+            .run(async || {
+                Ok(self
+                    ._stat(StatRequest {
+                        location,
+                        options: None,
+                    })
+                    .await
+                    .map(Json)?)
+            })
+            .await?;
+
+        Ok(Json(AppendResponse {
+            content_length: metadata.content_length.unwrap_or_default(),
+        }))
+    }
+
+    /// Delete a given location.
+    async fn delete(
+        &self,
+        ctx: Context<'_>,
+        request: Json<DeleteRequest>,
+    ) -> HandlerResult<Json<DeleteResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._delete(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Remove all entries under a given location recursively.
+    async fn remove_all(
+        &self,
+        ctx: Context<'_>,
+        request: Json<RemoveAllRequest>,
+    ) -> HandlerResult<Json<DeleteResponse>> {
+        let request = request.into_inner();
+        let batch_size = request.batch_size() as u64;
+        let mut deleted = 0;
+
+        // Every batch is journaled on its own, so a retry resumes with the remaining entries.
+        loop {
+            let Json(batch) = ctx
+                .run(async || Ok(self._remove_all(request.clone()).await.map(Json)?))
+                .await?;
+
+            deleted += batch.deleted;
+
+            if batch.deleted < batch_size {
+                break;
+            }
+        }
+
+        Ok(Json(DeleteResponse { deleted }))
+    }
+
+    /// Delete a list of locations.
+    async fn delete_batch(
+        &self,
+        ctx: Context<'_>,
+        request: Json<DeleteBatchRequest>,
+    ) -> HandlerResult<Json<DeleteBatchResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._delete_batch(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Copy a file within the store.
+    async fn copy(&self, ctx: Context<'_>, request: Json<CopyRequest>) -> HandlerResult<()> {
+        ctx.run(async || Ok(self._copy(request.into_inner()).await?))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Rename a file within the store.
+    async fn rename(&self, ctx: Context<'_>, request: Json<RenameRequest>) -> HandlerResult<()> {
+        ctx.run(async || Ok(self._rename(request.into_inner()).await?))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Export a recursive listing of a location into another location.
+    async fn export_inventory(
+        &self,
+        ctx: Context<'_>,
+        request: Json<ExportInventoryRequest>,
+    ) -> HandlerResult<Json<ExportInventoryResponse>> {
+        Ok(ctx
+            .run(async || {
+                Ok(self
+                    ._export_inventory(request.into_inner())
+                    .await
+                    .map(Json)?)
+            })
+            .await?)
+    }
+
+    /// Retrieve information and capabilities of the store behind a given location.
+    async fn capabilities(
+        &self,
+        ctx: Context<'_>,
+        request: Json<CapabilitiesRequest>,
+    ) -> HandlerResult<Json<CapabilitiesResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._capabilities(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
     /// Presign an operation for read.
     async fn presign_read(
         &self,
@@ -69,4 +317,26 @@ impl Service for ServiceImpl {
             .run(async || Ok(self._presign_stat(request.into_inner()).await.map(Json)?))
             .await?)
     }
+
+    /// Presign an operation for write.
+    async fn presign_write(
+        &self,
+        ctx: Context<'_>,
+        request: Json<PresignWriteRequest>,
+    ) -> HandlerResult<Json<PresignResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._presign_write(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
+
+    /// Presign an operation for delete.
+    async fn presign_delete(
+        &self,
+        ctx: Context<'_>,
+        request: Json<PresignDeleteRequest>,
+    ) -> HandlerResult<Json<PresignResponse>> {
+        Ok(ctx
+            .run(async || Ok(self._presign_delete(request.into_inner()).await.map(Json)?))
+            .await?)
+    }
 }